@@ -0,0 +1,81 @@
+//! Parses generated sources of doubling size and prints the time per token.
+//! A linear parser keeps the per-token time flat as the input grows, and the
+//! bench fails if it grows more than `MAX_GROWTH` times.
+//!
+//! Run with `cargo bench --bench parser`.
+
+use std::time::{Duration, Instant};
+
+use yoyogay::{
+    parser::{expr::parse_expr, parse_tks},
+    tokenizer::tokenize,
+};
+
+const RUNS: u32 = 5;
+/// How many times the time per token may grow from the smallest input to the
+/// largest. Their sizes are 8 times apart or more, so a quadratic parser goes
+/// well past it while timing noise doesn't.
+const MAX_GROWTH: f64 = 4.0;
+
+fn generated_file(statements: usize) -> String {
+    (0..statements)
+        .map(|i| format!("x{} = a + b * (c - d) / f(e, g[{}]) - h.i % -k;\n", i, i))
+        .collect()
+}
+
+fn generated_expr(terms: usize) -> String {
+    (0..terms)
+        .map(|i| format!("v{}[{}].w * {}", i, i, i))
+        .collect::<Vec<String>>()
+        .join(" + ")
+}
+
+fn time<F: Fn()>(f: F) -> Duration {
+    let start = Instant::now();
+    for _ in 0..RUNS {
+        f();
+    }
+
+    start.elapsed() / RUNS
+}
+
+fn report(name: &str, sizes: &[usize], generate: fn(usize) -> String, whole_program: bool) {
+    println!("{}", name);
+
+    let mut first_per_token: Option<f64> = None;
+    let mut ratio = 1.0;
+    for size in sizes {
+        let src = generate(*size);
+        let tokens = tokenize(&src).expect("Generated source should tokenize");
+
+        let elapsed = time(|| {
+            if whole_program {
+                assert!(parse_tks(&tokens).1.is_empty(), "Generated source should parse");
+            } else {
+                parse_expr(&tokens).expect("Generated expression should parse");
+            }
+        });
+
+        let per_token = elapsed.as_nanos() as f64 / tokens.len() as f64;
+        ratio = per_token / *first_per_token.get_or_insert(per_token);
+        println!(
+            "  {:>8} tokens  {:>10.3} ms  {:>8.1} ns/token  x{:.2}",
+            tokens.len(),
+            elapsed.as_secs_f64() * 1000.0,
+            per_token,
+            ratio
+        );
+    }
+
+    assert!(
+        ratio <= MAX_GROWTH,
+        "{}: time per token grew x{:.2}, parsing is no longer linear",
+        name,
+        ratio
+    );
+}
+
+fn main() {
+    report("statements", &[1_000, 2_000, 4_000, 8_000, 16_000], generated_file, true);
+    report("single expression", &[500, 1_000, 2_000, 4_000], generated_expr, false);
+}
@@ -1,6 +1,9 @@
+use std::fmt;
+
 use crate::{
+    parser::types::YoyogayType,
     text_data::BorrowedTextRange,
-    tokenizer::{Token, TokenData},
+    tokenizer::{NumericLiteral, Token, TokenData},
 };
 
 #[derive(Debug)]
@@ -13,25 +16,62 @@ pub struct Node<'a> {
 pub enum NodeData<'a> {
     Program(Vec<Node<'a>>),
     Identifier(&'a String),
-    NumericLiteral(&'a usize),
+    NumericLiteral(&'a NumericLiteral),
     String(&'a String),
     BinaryExpr(Node<'a>, &'a OperatorType, Node<'a>),
     Tuple(Vec<Node<'a>>),
-    FunctionDeclaration(Node<'a>, Vec<Node<'a>>, Node<'a>),
+    /// Name, parameters, return `Type`, body and doc comment.
+    FunctionDeclaration(
+        Node<'a>,
+        Vec<Node<'a>>,
+        Option<Node<'a>>,
+        Node<'a>,
+        DocComment<'a>,
+    ),
     FunctionCall(Node<'a>, Vec<Node<'a>>),
-    FunctionParemeter(Node<'a>),
+    FunctionParemeter(Node<'a>, Option<Node<'a>>),
+    /// The else node is an `Else`, or the `If` of an `else if`.
     If(Node<'a>, Node<'a>, Option<Node<'a>>),
     Else(Node<'a>),
-    VariableDeclaration(DeclarationType, Node<'a>, Node<'a>),
+    While(Node<'a>, Node<'a>),
+    Loop(Node<'a>),
+    Repeat(Node<'a>, Node<'a>),
+    DoUntil(Node<'a>, Node<'a>),
+    Break,
+    Continue,
+    Return(Option<Node<'a>>),
+    /// `super();`, which runs the parent object's code for the same event.
+    Super,
+    For(Node<'a>, Node<'a>, Node<'a>),
+    Range(Node<'a>, Node<'a>, RangeKind, Option<Node<'a>>),
+    VariableDeclaration(
+        DeclarationType,
+        Node<'a>,
+        Option<Node<'a>>,
+        Node<'a>,
+        DocComment<'a>,
+    ),
     VariableModification(Node<'a>, VariableModificationType, Node<'a>),
+    /// A call or access used on its own, like `instance_destroy();`.
+    ExprStatement(Node<'a>),
     StructAccess(Node<'a>, Node<'a>),
     ModAccess(Node<'a>, Node<'a>),
     ArrayAccess(Node<'a>, Node<'a>),
     Neg(Node<'a>),
-    Type
+    Not(Node<'a>),
+    BitNot(Node<'a>),
+    /// A type annotation.
+    Type(YoyogayType<'a>),
+    /// Placeholder for a statement that failed to parse.
+    Error,
 }
 
-#[derive(Debug)]
+/// Lines of the `///` comments written right before a declaration.
+pub type DocComment<'a> = Vec<&'a String>;
+
+/// `const` and `let` are block-scoped locals, `const` ones can't be
+/// reassigned. `var` declares an instance variable on the object.
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum DeclarationType {
     Const,
     Var,
@@ -44,6 +84,7 @@ impl<'a> TryFrom<&'a Token> for DeclarationType {
         match value.data {
             TokenData::Const => Ok(DeclarationType::Const),
             TokenData::Var => Ok(DeclarationType::Var),
+            TokenData::Let => Ok(DeclarationType::Let),
             _ => Err(value),
         }
     }
@@ -55,26 +96,79 @@ pub enum OperatorType {
     Sub,
     Mul,
     Div,
+    IntDiv,
+    Mod,
+
+    ShiftLeft,
+    ShiftRight,
+    BitAnd,
+    BitOr,
+    BitXor,
 
+    LessThan,
+    GreaterThan,
+    LessOrEquals,
+    GreaterOrEquals,
     NotEquals,
     IsEquals,
+
+    And,
+    Xor,
     Or,
 }
 
-impl ToString for OperatorType {
-    fn to_string(&self) -> String {
+impl OperatorType {
+    /// Binding power of the operator, higher binds tighter. Follows the GML
+    /// ordering: logical < comparison < bitwise < shift < additive < multiplicative.
+    pub fn precedence(&self) -> u8 {
         match self {
-            OperatorType::Add => "+".to_string(),
-            OperatorType::Sub => "-".to_string(),
-            OperatorType::Mul => "*".to_string(),
-            OperatorType::Div => "/".to_string(),
-            OperatorType::NotEquals => "!=".to_string(),
-            OperatorType::IsEquals => "==".to_string(),
-            OperatorType::Or => "||".to_string(),
+            OperatorType::Or => 1,
+            OperatorType::Xor => 2,
+            OperatorType::And => 3,
+            OperatorType::IsEquals | OperatorType::NotEquals => 4,
+            OperatorType::LessThan
+            | OperatorType::GreaterThan
+            | OperatorType::LessOrEquals
+            | OperatorType::GreaterOrEquals => 5,
+            OperatorType::BitOr => 6,
+            OperatorType::BitXor => 7,
+            OperatorType::BitAnd => 8,
+            OperatorType::ShiftLeft | OperatorType::ShiftRight => 9,
+            OperatorType::Add | OperatorType::Sub => 10,
+            OperatorType::Mul | OperatorType::Div | OperatorType::IntDiv | OperatorType::Mod => 11,
         }
     }
 }
 
+impl fmt::Display for OperatorType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let txt = match self {
+            OperatorType::Add => "+",
+            OperatorType::Sub => "-",
+            OperatorType::Mul => "*",
+            OperatorType::Div => "/",
+            OperatorType::NotEquals => "!=",
+            OperatorType::IsEquals => "==",
+            OperatorType::IntDiv => "div",
+            OperatorType::Mod => "%",
+            OperatorType::ShiftLeft => "<<",
+            OperatorType::ShiftRight => ">>",
+            OperatorType::BitAnd => "&",
+            OperatorType::BitOr => "|",
+            OperatorType::BitXor => "^",
+            OperatorType::LessThan => "<",
+            OperatorType::GreaterThan => ">",
+            OperatorType::LessOrEquals => "<=",
+            OperatorType::GreaterOrEquals => ">=",
+            OperatorType::And => "&&",
+            OperatorType::Xor => "^^",
+            OperatorType::Or => "||",
+        };
+
+        f.write_str(txt)
+    }
+}
+
 #[derive(Debug, PartialEq)]
 pub enum VariableModificationType {
     IncreaseBy,
@@ -84,15 +178,34 @@ pub enum VariableModificationType {
     Set,
 }
 
-impl ToString for VariableModificationType {
-    fn to_string(&self) -> String {
-        match self {
+impl fmt::Display for VariableModificationType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let txt = match self {
             VariableModificationType::IncreaseBy => "+=",
             VariableModificationType::DecreaseBy => "-=",
             VariableModificationType::MultiplyBy => "*=",
             VariableModificationType::DivideBy => "/=",
             VariableModificationType::Set => "=",
-        }.to_string()
+        };
+
+        f.write_str(txt)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RangeKind {
+    Exclusive,
+    Inclusive,
+}
+
+impl fmt::Display for RangeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let txt = match self {
+            RangeKind::Exclusive => "..",
+            RangeKind::Inclusive => "..=",
+        };
+
+        f.write_str(txt)
     }
 }
 
@@ -102,11 +215,12 @@ pub enum PropertyAccessType {
     Mod,
 }
 
-impl<'a> ToString for Node<'a> {
-    fn to_string(&self) -> String {
-        match &*self.data {
+impl fmt::Display for Node<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let txt = match &*self.data {
             NodeData::Identifier(id) => id.to_string(),
-            NodeData::NumericLiteral(num) => num.to_string(),
+            NodeData::NumericLiteral(num) => num.spelling.clone(),
+            NodeData::String(txt) => format!("{:?}", txt),
             NodeData::FunctionCall(id, params) => {
                 let mut params_txt = "".to_string();
                 for (i, param) in params.iter().enumerate() {
@@ -115,25 +229,34 @@ impl<'a> ToString for Node<'a> {
                         params_txt.push_str(", ");
                     }
                 }
-                format!("{}({})", id.to_string(), params_txt)
+                format!("{}({})", id, params_txt)
             }
             NodeData::StructAccess(struct_node, prop) => {
-                format!("{}.{}", struct_node.to_string(), prop.to_string())
+                format!("{}.{}", struct_node, prop)
             }
             NodeData::ModAccess(mod_node, prop) => {
-                format!("{}.{}", mod_node.to_string(), prop.to_string())
+                format!("{}.{}", mod_node, prop)
             }
             NodeData::ArrayAccess(arr_node, idx_node) => {
-                format!("{}[{}]", arr_node.to_string(), idx_node.to_string())
+                format!("{}[{}]", arr_node, idx_node)
             }
             NodeData::BinaryExpr(a, op, b) => {
-                format!("({}) {} ({})", a.to_string(), op.to_string(), b.to_string())
+                format!("({}) {} ({})", a, op, b)
             }
-            NodeData::VariableDeclaration(declaration_type, id, expr) => format!(
-                "{} {} = {};",
-                declaration_type.to_string(),
-                id.to_string(),
-                expr.to_string()
+            NodeData::VariableDeclaration(declaration_type, id, Some(r#type), expr, doc) => format!(
+                "{}{} {}: {} = {};",
+                doc_comment_to_string(doc),
+                declaration_type,
+                id,
+                r#type,
+                expr
+            ),
+            NodeData::VariableDeclaration(declaration_type, id, None, expr, doc) => format!(
+                "{}{} {} = {};",
+                doc_comment_to_string(doc),
+                declaration_type,
+                id,
+                expr
             ),
             NodeData::Program(statements) => {
                 let mut txt = String::new();
@@ -159,14 +282,15 @@ impl<'a> ToString for Node<'a> {
                     }
                 }
 
-                txt.push_str(")");
+                txt.push(')');
 
                 txt
             }
-            NodeData::FunctionDeclaration(name, args, body) => {
-                let mut txt = "fn ".to_string();
+            NodeData::FunctionDeclaration(name, args, return_type, body, doc) => {
+                let mut txt = doc_comment_to_string(doc);
+                txt.push_str("fn ");
                 txt.push_str(&name.to_string());
-                txt.push_str("(");
+                txt.push('(');
 
                 for (i, arg) in args.iter().enumerate() {
                     txt.push_str(&arg.to_string());
@@ -174,23 +298,31 @@ impl<'a> ToString for Node<'a> {
                         txt.push_str(", ");
                     }
                 }
-                txt.push_str(") {\n    ");
+                txt.push(')');
+                if let Some(return_type) = return_type {
+                    txt.push_str(&format!(" -> {}", return_type));
+                }
+                txt.push_str(" {\n    ");
                 txt.push_str(&body.to_string().replace("\n", "\n    "));
                 txt.push_str("\n}");
 
                 txt
             }
-            NodeData::FunctionParemeter(param) => param.to_string(),
-            NodeData::Neg(expr) => format!("(-{})", expr.to_string()),
+            NodeData::FunctionParemeter(param, Some(r#type)) => format!("{}: {}", param, r#type),
+            NodeData::FunctionParemeter(param, None) => param.to_string(),
+            NodeData::Type(r#type) => r#type.to_string(),
+            NodeData::Neg(expr) => format!("(-{})", expr),
+            NodeData::Not(expr) => format!("(!{})", expr),
+            NodeData::BitNot(expr) => format!("(~{})", expr),
             NodeData::If(condition, body, else_node) => {
-                let mut txt = format!("if {} {{\n    ", condition.to_string());
+                let mut txt = format!("if {} {{\n    ", condition);
                 txt.push_str(&body.to_string().replace("\n", "\n    "));
                 txt.push_str("\n}");
 
                 if let Some(node) = else_node {
                     match *node.data {
-                        NodeData::Else(_) => txt.push_str(&format!(" {}", node.to_string())),
-                        _ => panic!("Else node wasnt an else node"),
+                        NodeData::Else(_) => txt.push_str(&format!(" {}", node)),
+                        _ => txt.push_str(&format!(" else {}", node)),
                     }
                 }
 
@@ -205,22 +337,56 @@ impl<'a> ToString for Node<'a> {
             }
             NodeData::VariableModification(id, mod_type, value) => format!(
                 "{} {} {};",
-                id.to_string(),
-                mod_type.to_string(),
-                value.to_string()
+                id,
+                mod_type,
+                value
             ),
 
-            _ => panic!("unhandled: {:?}", self.data),
-        }
+            NodeData::While(condition, body) => {
+                format!("while {} {}", condition, block_to_string(body))
+            }
+            NodeData::Loop(body) => format!("loop {}", block_to_string(body)),
+            NodeData::Repeat(count, body) => format!("repeat {} {}", count, block_to_string(body)),
+            NodeData::DoUntil(body, condition) => {
+                format!("do {} until {};", block_to_string(body), condition)
+            }
+            NodeData::For(variable, iterable, body) => {
+                format!("for {} in {} {}", variable, iterable, block_to_string(body))
+            }
+            NodeData::Range(start, end, kind, None) => format!("{}{}{}", start, kind, end),
+            NodeData::Range(start, end, kind, Some(step)) => {
+                format!("({}{}{}).step({})", start, kind, end, step)
+            }
+            NodeData::Return(Some(value)) => format!("return {};", value),
+            NodeData::Return(None) => "return;".to_string(),
+            NodeData::Break => "break;".to_string(),
+            NodeData::Continue => "continue;".to_string(),
+            NodeData::Super => "super();".to_string(),
+            NodeData::ExprStatement(expr) => format!("{};", expr),
+            NodeData::Error => "<error>".to_string(),
+
+        };
+
+        f.write_str(&txt)
     }
 }
 
-impl ToString for DeclarationType {
-    fn to_string(&self) -> String {
-        match self {
-            DeclarationType::Var => "var".to_string(),
-            DeclarationType::Const => "const".to_string(),
-            DeclarationType::Let => "let".to_string(),
-        }
+fn block_to_string(body: &Node) -> String {
+    format!("{{\n    {}\n}}", body.to_string().replace("\n", "\n    "))
+}
+
+fn doc_comment_to_string(doc: &DocComment) -> String {
+    doc.iter().map(|line| format!("/// {}\n", line)).collect()
+}
+
+impl fmt::Display for DeclarationType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let txt = match self {
+            DeclarationType::Var => "var",
+            DeclarationType::Const => "const",
+            DeclarationType::Let => "let",
+        };
+
+        f.write_str(txt)
     }
 }
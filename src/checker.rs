@@ -0,0 +1,3 @@
+pub mod builtins;
+pub mod resolver;
+pub mod type_check;
@@ -0,0 +1,393 @@
+/// Built-in instance variables every object has.
+pub const INSTANCE_VARIABLES: &[&str] = &[
+    "id",
+    "object_index",
+    "x",
+    "y",
+    "xstart",
+    "ystart",
+    "xprevious",
+    "yprevious",
+    "speed",
+    "direction",
+    "hspeed",
+    "vspeed",
+    "gravity",
+    "gravity_direction",
+    "friction",
+    "depth",
+    "layer",
+    "visible",
+    "solid",
+    "persistent",
+    "alarm",
+    "sprite_index",
+    "sprite_width",
+    "sprite_height",
+    "sprite_xoffset",
+    "sprite_yoffset",
+    "image_index",
+    "image_speed",
+    "image_number",
+    "image_xscale",
+    "image_yscale",
+    "image_angle",
+    "image_alpha",
+    "image_blend",
+    "mask_index",
+    "bbox_left",
+    "bbox_right",
+    "bbox_top",
+    "bbox_bottom",
+    "path_index",
+    "path_position",
+    "path_speed",
+];
+
+/// Built-in global variables and constants.
+pub const CONSTANTS: &[&str] = &[
+    "self",
+    "other",
+    "all",
+    "noone",
+    "global",
+    "true",
+    "false",
+    "undefined",
+    "pi",
+    "infinity",
+    "NaN",
+    "room",
+    "room_width",
+    "room_height",
+    "room_speed",
+    "fps",
+    "fps_real",
+    "current_time",
+    "delta_time",
+    "mouse_x",
+    "mouse_y",
+    "keyboard_key",
+    "keyboard_lastkey",
+    "keyboard_lastchar",
+    "keyboard_string",
+    "view_camera",
+    "view_enabled",
+    "view_visible",
+    "async_load",
+    "event_type",
+    "event_number",
+    "os_type",
+    "browser_width",
+    "browser_height",
+];
+
+/// Prefixes of the families of built-in constants, like `vk_left` or `c_white`.
+pub const CONSTANT_PREFIXES: &[&str] = &[
+    "vk_",
+    "mb_",
+    "gp_",
+    "c_",
+    "fa_",
+    "bm_",
+    "ev_",
+    "ds_type_",
+    "os_",
+    "buffer_",
+    "network_",
+    "tm_",
+    "cr_",
+    "pr_",
+    "ty_",
+    "audio_",
+    "layerelementtype_",
+    "asset_",
+];
+
+/// Built-in functions.
+pub const FUNCTIONS: &[&str] = &[
+    // Instances
+    "instance_create",
+    "instance_create_depth",
+    "instance_create_layer",
+    "instance_destroy",
+    "instance_exists",
+    "instance_number",
+    "instance_find",
+    "instance_nearest",
+    "instance_furthest",
+    "instance_place",
+    "instance_position",
+    "instance_change",
+    "instance_copy",
+    "instance_activate_all",
+    "instance_deactivate_all",
+    "instance_activate_object",
+    "instance_deactivate_object",
+    "event_inherited",
+    "event_perform",
+    "event_user",
+    "object_get_name",
+    "object_get_parent",
+    "object_is_ancestor",
+    // Movement and collisions
+    "place_meeting",
+    "place_free",
+    "place_empty",
+    "position_meeting",
+    "position_empty",
+    "collision_point",
+    "collision_rectangle",
+    "collision_circle",
+    "collision_line",
+    "move_towards_point",
+    "move_contact_solid",
+    "move_outside_solid",
+    "move_bounce_solid",
+    "motion_add",
+    "motion_set",
+    "point_direction",
+    "point_distance",
+    "distance_to_object",
+    "distance_to_point",
+    "lengthdir_x",
+    "lengthdir_y",
+    "angle_difference",
+    // Input
+    "keyboard_check",
+    "keyboard_check_pressed",
+    "keyboard_check_released",
+    "keyboard_clear",
+    "mouse_check_button",
+    "mouse_check_button_pressed",
+    "mouse_check_button_released",
+    "gamepad_is_connected",
+    "gamepad_button_check",
+    "gamepad_button_check_pressed",
+    "gamepad_axis_value",
+    // Drawing
+    "draw_self",
+    "draw_sprite",
+    "draw_sprite_ext",
+    "draw_sprite_stretched",
+    "draw_sprite_part",
+    "draw_text",
+    "draw_text_ext",
+    "draw_text_transformed",
+    "draw_text_colour",
+    "draw_rectangle",
+    "draw_rectangle_colour",
+    "draw_circle",
+    "draw_line",
+    "draw_line_width",
+    "draw_point",
+    "draw_healthbar",
+    "draw_set_colour",
+    "draw_set_color",
+    "draw_set_alpha",
+    "draw_set_font",
+    "draw_set_halign",
+    "draw_set_valign",
+    "draw_get_colour",
+    "draw_get_alpha",
+    "draw_clear",
+    "draw_surface",
+    "surface_create",
+    "surface_exists",
+    "surface_free",
+    "surface_set_target",
+    "surface_reset_target",
+    "gpu_set_blendmode",
+    "shader_set",
+    "shader_reset",
+    "make_colour_rgb",
+    "make_color_rgb",
+    "make_colour_hsv",
+    "merge_colour",
+    "sprite_get_width",
+    "sprite_get_height",
+    "sprite_get_number",
+    "string_width",
+    "string_height",
+    // Maths
+    "abs",
+    "sign",
+    "round",
+    "floor",
+    "ceil",
+    "frac",
+    "sqr",
+    "sqrt",
+    "power",
+    "exp",
+    "ln",
+    "log2",
+    "log10",
+    "sin",
+    "cos",
+    "tan",
+    "arcsin",
+    "arccos",
+    "arctan",
+    "arctan2",
+    "dsin",
+    "dcos",
+    "dtan",
+    "degtorad",
+    "radtodeg",
+    "min",
+    "max",
+    "mean",
+    "median",
+    "clamp",
+    "lerp",
+    "random",
+    "random_range",
+    "irandom",
+    "irandom_range",
+    "choose",
+    "randomize",
+    "random_set_seed",
+    "real",
+    "int64",
+    "bool",
+    "is_real",
+    "is_string",
+    "is_bool",
+    "is_array",
+    "is_struct",
+    "is_method",
+    "is_undefined",
+    "is_numeric",
+    "typeof",
+    // Strings
+    "string",
+    "string_length",
+    "string_char_at",
+    "string_copy",
+    "string_delete",
+    "string_insert",
+    "string_pos",
+    "string_replace",
+    "string_replace_all",
+    "string_upper",
+    "string_lower",
+    "string_repeat",
+    "string_digits",
+    "string_format",
+    "string_split",
+    "string_join",
+    "string_trim",
+    "string_count",
+    "chr",
+    "ord",
+    // Arrays and structs
+    "array_create",
+    "array_length",
+    "array_push",
+    "array_pop",
+    "array_insert",
+    "array_delete",
+    "array_copy",
+    "array_resize",
+    "array_sort",
+    "array_contains",
+    "array_get_index",
+    "array_map",
+    "array_filter",
+    "array_foreach",
+    "variable_instance_exists",
+    "variable_instance_get",
+    "variable_instance_set",
+    "variable_global_exists",
+    "variable_global_get",
+    "variable_global_set",
+    "variable_struct_exists",
+    "variable_struct_get",
+    "variable_struct_set",
+    "struct_exists",
+    "struct_get",
+    "struct_set",
+    "struct_get_names",
+    "method",
+    // Data structures
+    "ds_list_create",
+    "ds_list_destroy",
+    "ds_list_add",
+    "ds_list_find_value",
+    "ds_list_size",
+    "ds_list_delete",
+    "ds_list_clear",
+    "ds_map_create",
+    "ds_map_destroy",
+    "ds_map_add",
+    "ds_map_find_value",
+    "ds_map_exists",
+    "ds_map_delete",
+    "ds_grid_create",
+    "ds_grid_destroy",
+    "ds_grid_get",
+    "ds_grid_set",
+    "ds_stack_create",
+    "ds_queue_create",
+    "ds_priority_create",
+    // Alarms, rooms and game
+    "alarm_get",
+    "alarm_set",
+    "room_goto",
+    "room_goto_next",
+    "room_goto_previous",
+    "room_restart",
+    "game_end",
+    "game_restart",
+    "window_set_fullscreen",
+    "window_get_width",
+    "window_get_height",
+    "display_get_width",
+    "display_get_height",
+    "camera_get_view_x",
+    "camera_get_view_y",
+    "camera_set_view_pos",
+    "layer_get_id",
+    "layer_create",
+    "layer_destroy",
+    // Audio
+    "audio_play_sound",
+    "audio_stop_sound",
+    "audio_stop_all",
+    "audio_is_playing",
+    "audio_sound_gain",
+    "audio_sound_pitch",
+    // Time, files and debugging
+    "get_timer",
+    "date_current_datetime",
+    "time_source_create",
+    "time_source_start",
+    "call_later",
+    "file_exists",
+    "file_text_open_read",
+    "file_text_open_write",
+    "file_text_read_string",
+    "file_text_write_string",
+    "file_text_close",
+    "json_stringify",
+    "json_parse",
+    "ini_open",
+    "ini_close",
+    "ini_read_real",
+    "ini_write_real",
+    "show_debug_message",
+    "show_message",
+    "show_error",
+    "debug_get_callstack",
+    "script_execute",
+];
+
+/// Whether `name` is a built-in constant, global or function.
+pub fn is_builtin(name: &str) -> bool {
+    CONSTANTS.contains(&name)
+        || FUNCTIONS.contains(&name)
+        || CONSTANT_PREFIXES
+            .iter()
+            .any(|prefix| name.starts_with(prefix))
+}
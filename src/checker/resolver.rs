@@ -0,0 +1,451 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::Path,
+};
+
+use crate::{
+    ast::{DeclarationType, Node, NodeData},
+    organizer::info::ProjectInfo,
+    text_data::TextRange,
+};
+
+use super::builtins::{self, INSTANCE_VARIABLES};
+
+#[derive(Debug, PartialEq)]
+pub struct ResolveError {
+    pub data: ResolveErrorData,
+    pub text_range: TextRange,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ResolveErrorData {
+    /// The name and the closest known name, if any is close enough.
+    UndefinedName(String, Option<String>),
+    /// A declaration hiding a name from an outer scope or an instance
+    /// variable. Only a warning, since the code still does what it says.
+    ShadowedName(String),
+    DuplicateDeclaration(String),
+    ConstantReassignment(String),
+    /// Object (or `global`), the missing member and the closest existing one.
+    UnknownMember(String, String, Option<String>),
+}
+
+impl ResolveError {
+    /// Whether the project still compiles with it.
+    pub fn is_warning(&self) -> bool {
+        matches!(self.data, ResolveErrorData::ShadowedName(_))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BindingKind {
+    Local,
+    Constant,
+    Parameter,
+    InstanceField,
+    Global,
+    Function,
+    Builtin,
+    /// Sprite, sound or other asset made in GameMaker.
+    Asset,
+}
+
+/// What an identifier used in the program refers to.
+#[derive(Debug)]
+pub struct Binding {
+    pub name: String,
+    pub kind: BindingKind,
+    pub text_range: TextRange,
+}
+
+/// Binds every identifier in `program` to its declaration, reporting the ones
+/// that don't refer to anything. `fields` are the instance variables declared
+/// by the object outside of `program`, `other` the object whose members
+/// `other.member` refers to, if it is known.
+pub fn resolve_names(
+    program: &Node,
+    info: &ProjectInfo,
+    fields: &[String],
+    other: Option<&str>,
+) -> (Vec<Binding>, Vec<ResolveError>) {
+    resolve(program, info, fields, other, None)
+}
+
+/// Like `resolve_names`, for the script read from `path`. The functions and
+/// constants it declares are already in `info` and aren't reported as
+/// shadowing themselves.
+pub fn resolve_script(
+    program: &Node,
+    info: &ProjectInfo,
+    path: &Path,
+) -> (Vec<Binding>, Vec<ResolveError>) {
+    resolve(program, info, &[], None, Some(path))
+}
+
+fn resolve(
+    program: &Node,
+    info: &ProjectInfo,
+    fields: &[String],
+    other: Option<&str>,
+    script: Option<&Path>,
+) -> (Vec<Binding>, Vec<ResolveError>) {
+    let mut globals: HashMap<String, BindingKind> = HashMap::new();
+    for (name, function) in &info.functions {
+        if Some(function.location.path.as_path()) != script {
+            globals.insert(name.to_string(), BindingKind::Function);
+        }
+    }
+    for (name, constant) in &info.constants {
+        if Some(constant.location.path.as_path()) != script {
+            globals.insert(name.to_string(), BindingKind::Constant);
+        }
+    }
+    for name in info.objects.keys() {
+        globals.insert(name.to_string(), BindingKind::Global);
+    }
+    for name in &info.assets {
+        globals
+            .entry(name.to_string())
+            .or_insert(BindingKind::Asset);
+    }
+
+    let mut resolver = Resolver {
+        info,
+        scopes: vec![globals],
+        fields: fields.iter().cloned().collect(),
+        other: other.map(str::to_string),
+        bindings: Vec::new(),
+        errors: Vec::new(),
+    };
+    resolver.resolve_block(program);
+
+    (resolver.bindings, resolver.errors)
+}
+
+struct Resolver<'i, 'a> {
+    info: &'i ProjectInfo<'a>,
+    scopes: Vec<HashMap<String, BindingKind>>,
+    /// Instance variables aren't scoped to a block.
+    fields: HashSet<String>,
+    other: Option<String>,
+    bindings: Vec<Binding>,
+    errors: Vec<ResolveError>,
+}
+
+impl Resolver<'_, '_> {
+    fn error(&mut self, data: ResolveErrorData, node: &Node) {
+        self.errors.push(ResolveError {
+            data,
+            text_range: node.text_range.clone().into(),
+        });
+    }
+
+    fn lookup(&self, name: &str) -> Option<BindingKind> {
+        if let Some(kind) = self.scopes.iter().rev().find_map(|scope| scope.get(name)) {
+            return Some(*kind);
+        }
+
+        if self.fields.contains(name) || INSTANCE_VARIABLES.contains(&name) {
+            Some(BindingKind::InstanceField)
+        } else if builtins::is_builtin(name) {
+            Some(BindingKind::Builtin)
+        } else {
+            None
+        }
+    }
+
+    fn declare(&mut self, id: &Node, kind: BindingKind) {
+        let NodeData::Identifier(name) = &*id.data else {
+            return;
+        };
+
+        let (current, outer) = match self.scopes.split_last() {
+            Some(scopes) => scopes,
+            None => return,
+        };
+
+        if current.contains_key(name.as_str()) {
+            self.error(ResolveErrorData::DuplicateDeclaration(name.to_string()), id);
+        } else if outer.iter().any(|scope| scope.contains_key(name.as_str())) || self.is_field(name)
+        {
+            self.error(ResolveErrorData::ShadowedName(name.to_string()), id);
+        }
+
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(name.to_string(), kind);
+        }
+    }
+
+    fn declare_field(&mut self, id: &Node) {
+        let NodeData::Identifier(name) = &*id.data else {
+            return;
+        };
+
+        if self.is_field(name) {
+            self.error(ResolveErrorData::DuplicateDeclaration(name.to_string()), id);
+        } else if self
+            .scopes
+            .iter()
+            .any(|scope| scope.contains_key(name.as_str()))
+        {
+            // Assigning to the name would change the local instead.
+            self.error(ResolveErrorData::ShadowedName(name.to_string()), id);
+        }
+
+        self.fields.insert(name.to_string());
+    }
+
+    fn is_field(&self, name: &str) -> bool {
+        self.fields.contains(name) || INSTANCE_VARIABLES.contains(&name)
+    }
+
+    fn check_reassignment(&mut self, target: &Node) {
+        match &*target.data {
+            NodeData::Identifier(name) if self.lookup(name) == Some(BindingKind::Constant) => {
+                self.error(
+                    ResolveErrorData::ConstantReassignment(name.to_string()),
+                    target,
+                );
+            }
+            NodeData::Tuple(targets) => {
+                for target in targets {
+                    self.check_reassignment(target);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn resolve_name(&mut self, name: &str, node: &Node) {
+        match self.lookup(name) {
+            Some(kind) => self.bindings.push(Binding {
+                name: name.to_string(),
+                kind,
+                text_range: node.text_range.clone().into(),
+            }),
+            None => {
+                let suggestion = self.closest_name(name);
+                self.error(
+                    ResolveErrorData::UndefinedName(name.to_string(), suggestion),
+                    node,
+                );
+            }
+        }
+    }
+
+    /// The visible name closest to `name`, if it is close enough to be a typo.
+    fn closest_name(&self, name: &str) -> Option<String> {
+        let declared = self
+            .scopes
+            .iter()
+            .rev()
+            .flat_map(|scope| scope.keys().map(String::as_str))
+            .chain(self.fields.iter().map(String::as_str));
+        let builtin = INSTANCE_VARIABLES
+            .iter()
+            .chain(builtins::CONSTANTS)
+            .chain(builtins::FUNCTIONS)
+            .copied();
+
+        closest(name, declared.chain(builtin))
+    }
+
+    /// Checks that `value::property` exists when `value` is an object of the
+    /// project, `global` or an `other` whose object is known. Any other
+    /// property is looked up at runtime.
+    fn check_member(&mut self, value: &Node, property: &Node) {
+        let (NodeData::Identifier(id), NodeData::Identifier(name)) =
+            (&*value.data, &*property.data)
+        else {
+            return;
+        };
+
+        let info = self.info;
+        let (exists, candidates): (bool, Vec<&str>) = match self.lookup(id) {
+            Some(BindingKind::Builtin) if id.as_str() == "global" => (
+                info.globals.contains_key(name.as_str()),
+                info.globals.keys().map(String::as_str).collect(),
+            ),
+            Some(BindingKind::Builtin) if id.as_str() == "other" && self.other.is_some() => {
+                match self.other.as_deref() {
+                    Some(other) if info.object(other).is_some() => {
+                        object_members(info, other, name)
+                    }
+                    _ => return,
+                }
+            }
+            Some(BindingKind::Global) if info.object(id).is_some() => {
+                object_members(info, id, name)
+            }
+            _ => return,
+        };
+
+        if !exists {
+            let suggestion = closest(name, candidates.into_iter());
+            self.error(
+                ResolveErrorData::UnknownMember(id.to_string(), name.to_string(), suggestion),
+                property,
+            );
+        }
+    }
+
+    fn resolve_block(&mut self, node: &Node) {
+        let NodeData::Program(statements) = &*node.data else {
+            return self.resolve_stmt(node);
+        };
+
+        self.scopes.push(HashMap::new());
+
+        // Functions can be called before the statement declaring them.
+        for stmt in statements {
+            if let NodeData::FunctionDeclaration(name, ..) = &*stmt.data {
+                self.declare(name, BindingKind::Function);
+            }
+        }
+
+        for stmt in statements {
+            self.resolve_stmt(stmt);
+        }
+
+        self.scopes.pop();
+    }
+
+    fn resolve_stmt(&mut self, node: &Node) {
+        match &*node.data {
+            NodeData::Program(_) => self.resolve_block(node),
+            NodeData::VariableDeclaration(declaration_type, id, _, value, _) => {
+                self.resolve_expr(value);
+                match declaration_type {
+                    DeclarationType::Let => self.declare(id, BindingKind::Local),
+                    DeclarationType::Const => self.declare(id, BindingKind::Constant),
+                    DeclarationType::Var => self.declare_field(id),
+                }
+            }
+            NodeData::VariableModification(target, _, value) => {
+                self.resolve_expr(value);
+                self.resolve_expr(target);
+                self.check_reassignment(target);
+            }
+            NodeData::FunctionDeclaration(_, params, _, body, _) => {
+                self.scopes.push(HashMap::new());
+                for param in params {
+                    if let NodeData::FunctionParemeter(id, _) = &*param.data {
+                        self.declare(id, BindingKind::Parameter);
+                    }
+                }
+                self.resolve_block(body);
+                self.scopes.pop();
+            }
+            NodeData::If(condition, body, else_node) => {
+                self.resolve_expr(condition);
+                self.resolve_block(body);
+
+                if let Some(else_node) = else_node {
+                    match &*else_node.data {
+                        NodeData::Else(body) => self.resolve_block(body),
+                        _ => self.resolve_stmt(else_node),
+                    }
+                }
+            }
+            NodeData::While(condition, body) | NodeData::DoUntil(body, condition) => {
+                self.resolve_expr(condition);
+                self.resolve_block(body);
+            }
+            NodeData::Repeat(count, body) => {
+                self.resolve_expr(count);
+                self.resolve_block(body);
+            }
+            NodeData::Loop(body) => self.resolve_block(body),
+            NodeData::For(variable, iterable, body) => {
+                self.resolve_expr(iterable);
+
+                self.scopes.push(HashMap::new());
+                self.declare(variable, BindingKind::Local);
+                self.resolve_block(body);
+                self.scopes.pop();
+            }
+            NodeData::Return(Some(value)) | NodeData::ExprStatement(value) => {
+                self.resolve_expr(value)
+            }
+            _ => {}
+        }
+    }
+
+    fn resolve_expr(&mut self, node: &Node) {
+        match &*node.data {
+            NodeData::Identifier(name) => self.resolve_name(name, node),
+            NodeData::BinaryExpr(a, _, b) | NodeData::ArrayAccess(a, b) => {
+                self.resolve_expr(a);
+                self.resolve_expr(b);
+            }
+            NodeData::Tuple(values) => {
+                for value in values {
+                    self.resolve_expr(value);
+                }
+            }
+            NodeData::FunctionCall(callee, args) => {
+                self.resolve_expr(callee);
+                for arg in args {
+                    self.resolve_expr(arg);
+                }
+            }
+            NodeData::StructAccess(value, property) | NodeData::ModAccess(value, property) => {
+                self.resolve_expr(value);
+                self.check_member(value, property);
+            }
+            NodeData::Neg(value) | NodeData::Not(value) | NodeData::BitNot(value) => {
+                self.resolve_expr(value)
+            }
+            NodeData::Range(start, end, _, step) => {
+                self.resolve_expr(start);
+                self.resolve_expr(end);
+                if let Some(step) = step {
+                    self.resolve_expr(step);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Whether the object `id` has a member called `name`, and the names of its
+/// members.
+fn object_members<'i>(info: &'i ProjectInfo, id: &str, name: &str) -> (bool, Vec<&'i str>) {
+    (
+        info.member(id, name).is_some() || INSTANCE_VARIABLES.contains(&name),
+        info.members_of(id)
+            .into_iter()
+            .map(String::as_str)
+            .chain(INSTANCE_VARIABLES.iter().copied())
+            .collect(),
+    )
+}
+
+/// The candidate with the smallest edit distance to `name`, if it is close
+/// enough to be a typo.
+fn closest<'c>(name: &str, candidates: impl Iterator<Item = &'c str>) -> Option<String> {
+    let max_distance = (name.chars().count() / 3).max(1);
+
+    candidates
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate.to_string())
+}
+
+/// Levenshtein distance between two names.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, a_ch) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b_ch) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_ch != *b_ch);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
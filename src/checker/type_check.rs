@@ -0,0 +1,631 @@
+use std::{collections::HashMap, fmt};
+
+use crate::{
+    ast::{Node, NodeData, OperatorType, VariableModificationType},
+    organizer::info::{Member, ProjectInfo, YoyogayFunction},
+    parser::types::YoyogayType,
+    text_data::TextRange,
+    tokenizer::NumericValue,
+};
+
+#[derive(Debug)]
+pub struct TypeError {
+    pub data: TypeErrorData,
+    pub text_range: TextRange,
+}
+
+#[derive(Debug)]
+pub enum TypeErrorData {
+    /// Expected and found type.
+    MismatchedTypes(InferredType, InferredType),
+    /// Operator and the types of both of its operands.
+    InvalidOperands(String, InferredType, InferredType),
+    InvalidOperand(String, InferredType),
+    /// Expected and found number of arguments.
+    WrongArgumentCount(usize, usize),
+    /// The result of a function that returns nothing was used as a value.
+    VoidValue,
+    NotCallable(InferredType),
+    NotIndexable(InferredType),
+    NotIterable(InferredType),
+}
+
+/// Type of a value as far as the checker can tell.
+#[derive(Debug, Clone, PartialEq)]
+pub enum InferredType {
+    Real,
+    String,
+    Bool,
+    Array(Box<InferredType>),
+    Tuple(Vec<InferredType>),
+    /// Parameter types and return type.
+    Function(Vec<InferredType>, Box<InferredType>),
+    /// Any other named type, like a struct or an object.
+    Named(String, Vec<InferredType>),
+    /// What functions without a return type give back.
+    Void,
+    /// Anything the checker can't see through, like builtins and instance
+    /// variables. It is accepted everywhere.
+    Unknown,
+}
+
+impl InferredType {
+    /// Whether a value of type `found` can be used where `self` is expected.
+    pub fn accepts(&self, found: &InferredType) -> bool {
+        let all = |expected: &[InferredType], found: &[InferredType]| {
+            expected.len() == found.len() && expected.iter().zip(found).all(|(e, f)| e.accepts(f))
+        };
+
+        match (self, found) {
+            (InferredType::Void, InferredType::Void) => true,
+            (InferredType::Void, _) | (_, InferredType::Void) => false,
+            (InferredType::Unknown, _) | (_, InferredType::Unknown) => true,
+            (InferredType::Array(expected), InferredType::Array(found)) => expected.accepts(found),
+            // Tuples are arrays once compiled.
+            (InferredType::Array(expected), InferredType::Tuple(found)) => {
+                found.iter().all(|f| expected.accepts(f))
+            }
+            (InferredType::Tuple(expected), InferredType::Tuple(found)) => all(expected, found),
+            (
+                InferredType::Function(expected_params, expected_return),
+                InferredType::Function(found_params, found_return),
+            ) => all(found_params, expected_params) && expected_return.accepts(found_return),
+            (
+                InferredType::Named(expected, expected_args),
+                InferredType::Named(found, found_args),
+            ) => expected == found && all(expected_args, found_args),
+            _ => self == found,
+        }
+    }
+
+    fn is(&self, expected: &InferredType) -> bool {
+        self == expected || *self == InferredType::Unknown
+    }
+}
+
+impl From<&YoyogayType<'_>> for InferredType {
+    fn from(value: &YoyogayType) -> Self {
+        match value {
+            YoyogayType::Identifier(id, generics) => match (id.as_str(), generics.as_slice()) {
+                ("Real", []) => InferredType::Real,
+                ("String", []) => InferredType::String,
+                ("Bool", []) => InferredType::Bool,
+                ("Any", []) => InferredType::Unknown,
+                ("Void", []) => InferredType::Void,
+                ("Array", []) => InferredType::Array(Box::new(InferredType::Unknown)),
+                ("Array", [inner]) => InferredType::Array(Box::new(inner.into())),
+                (id, generics) => InferredType::Named(
+                    id.to_string(),
+                    generics.iter().map(InferredType::from).collect(),
+                ),
+            },
+            YoyogayType::Array(inner) => InferredType::Array(Box::new(inner.as_ref().into())),
+            YoyogayType::Tuple(types) => {
+                InferredType::Tuple(types.iter().map(InferredType::from).collect())
+            }
+        }
+    }
+}
+
+impl From<&YoyogayFunction<'_>> for InferredType {
+    fn from(value: &YoyogayFunction) -> Self {
+        let params = value
+            .params
+            .iter()
+            .map(|param| match &param.r#type {
+                Some(r#type) => r#type.into(),
+                None => InferredType::Unknown,
+            })
+            .collect();
+        let return_type = match &value.return_type {
+            Some(r#type) => r#type.into(),
+            None if value.returns_value => InferredType::Unknown,
+            None => InferredType::Void,
+        };
+
+        InferredType::Function(params, Box::new(return_type))
+    }
+}
+
+impl fmt::Display for InferredType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let join = |types: &[InferredType]| {
+            let types: Vec<String> = types.iter().map(|t| t.to_string()).collect();
+            types.join(", ")
+        };
+
+        let txt = match self {
+            InferredType::Real => "Real".to_string(),
+            InferredType::String => "String".to_string(),
+            InferredType::Bool => "Bool".to_string(),
+            InferredType::Array(inner) => format!("[{}]", inner),
+            InferredType::Tuple(types) => format!("({})", join(types)),
+            InferredType::Function(params, return_type) => {
+                format!("fn({}) -> {}", join(params), return_type)
+            }
+            InferredType::Named(id, generics) if generics.is_empty() => id.to_string(),
+            InferredType::Named(id, generics) => format!("{}<{}>", id, join(generics)),
+            InferredType::Void => "Void".to_string(),
+            InferredType::Unknown => "Any".to_string(),
+        };
+
+        f.write_str(&txt)
+    }
+}
+
+/// Checks the types in a parsed event or function body. Functions and object
+/// members in `info` can be used from anywhere in `program`. `other` is the
+/// object `other` is an instance of, if it is known.
+pub fn check_types(program: &Node, info: &ProjectInfo, other: Option<&str>) -> Vec<TypeError> {
+    let mut globals: HashMap<String, InferredType> = info
+        .functions
+        .iter()
+        .map(|(name, function)| (name.to_string(), function.into()))
+        .collect();
+    for (name, constant) in &info.constants {
+        let r#type = constant
+            .r#type
+            .as_ref()
+            .map_or(InferredType::Unknown, InferredType::from);
+        globals.entry(name.to_string()).or_insert(r#type);
+    }
+    if let Some(other) = other {
+        globals.insert(
+            "other".to_string(),
+            InferredType::Named(other.to_string(), Vec::new()),
+        );
+    }
+
+    let mut checker = TypeChecker {
+        info,
+        scopes: vec![globals],
+        return_types: Vec::new(),
+        errors: Vec::new(),
+    };
+    checker.check_block(program);
+
+    checker.errors
+}
+
+struct TypeChecker<'i, 'a> {
+    info: &'i ProjectInfo<'a>,
+    scopes: Vec<HashMap<String, InferredType>>,
+    /// Return types of the functions being checked, innermost last.
+    return_types: Vec<InferredType>,
+    errors: Vec<TypeError>,
+}
+
+impl TypeChecker<'_, '_> {
+    fn error(&mut self, data: TypeErrorData, node: &Node) {
+        self.errors.push(TypeError {
+            data,
+            text_range: node.text_range.clone().into(),
+        });
+    }
+
+    fn declare(&mut self, id: &Node, r#type: InferredType) {
+        if let (NodeData::Identifier(name), Some(scope)) = (&*id.data, self.scopes.last_mut()) {
+            scope.insert(name.to_string(), r#type);
+        }
+    }
+
+    fn lookup(&self, name: &str) -> Option<&InferredType> {
+        self.scopes.iter().rev().find_map(|scope| scope.get(name))
+    }
+
+    /// Reports `node` unless a value of type `found` fits in `expected`.
+    fn expect(&mut self, expected: &InferredType, found: &InferredType, node: &Node) {
+        if *found == InferredType::Void && *expected != InferredType::Void {
+            self.error(TypeErrorData::VoidValue, node);
+        } else if !expected.accepts(found) {
+            self.error(
+                TypeErrorData::MismatchedTypes(expected.clone(), found.clone()),
+                node,
+            );
+        }
+    }
+
+    fn check_block(&mut self, node: &Node) {
+        let NodeData::Program(statements) = &*node.data else {
+            return self.check_stmt(node);
+        };
+
+        self.scopes.push(HashMap::new());
+
+        // Functions can be called before the statement declaring them.
+        for stmt in statements {
+            if let NodeData::FunctionDeclaration(name, params, return_type, body, _) = &*stmt.data {
+                self.declare(name, function_type(params, return_type, body));
+            }
+        }
+
+        for stmt in statements {
+            self.check_stmt(stmt);
+        }
+
+        self.scopes.pop();
+    }
+
+    fn check_stmt(&mut self, node: &Node) {
+        match &*node.data {
+            NodeData::Program(_) => self.check_block(node),
+            NodeData::VariableDeclaration(_, id, r#type, value, _) => {
+                let found = self.infer(value);
+                let declared = match r#type {
+                    Some(r#type) => type_of_node(r#type),
+                    None => InferredType::Unknown,
+                };
+                self.expect(&declared, &found, value);
+
+                match (declared, found) {
+                    (InferredType::Unknown, InferredType::Void) => {
+                        self.declare(id, InferredType::Unknown)
+                    }
+                    (InferredType::Unknown, found) => self.declare(id, found),
+                    (declared, _) => self.declare(id, declared),
+                }
+            }
+            NodeData::VariableModification(target, modification, value) => {
+                let found = self.infer(value);
+                self.check_assignment(node, target, modification, &found, value);
+            }
+            NodeData::FunctionDeclaration(_, params, return_type, body, _) => {
+                self.scopes.push(HashMap::new());
+                for param in params {
+                    if let NodeData::FunctionParemeter(id, r#type) = &*param.data {
+                        let r#type = match r#type {
+                            Some(r#type) => type_of_node(r#type),
+                            None => InferredType::Unknown,
+                        };
+                        self.declare(id, r#type);
+                    }
+                }
+
+                let return_type = match function_type(params, return_type, body) {
+                    InferredType::Function(_, return_type) => *return_type,
+                    _ => InferredType::Unknown,
+                };
+                self.return_types.push(return_type);
+                self.check_block(body);
+                self.return_types.pop();
+
+                self.scopes.pop();
+            }
+            NodeData::Return(Some(value)) => {
+                let found = self.infer(value);
+                match self.return_types.last().cloned() {
+                    Some(InferredType::Void) | None => {}
+                    Some(expected) => self.expect(&expected, &found, value),
+                }
+            }
+            NodeData::If(condition, body, else_node) => {
+                self.check_condition(condition);
+                self.check_block(body);
+
+                if let Some(else_node) = else_node {
+                    match &*else_node.data {
+                        NodeData::Else(body) => self.check_block(body),
+                        _ => self.check_stmt(else_node),
+                    }
+                }
+            }
+            NodeData::While(condition, body) => {
+                self.check_condition(condition);
+                self.check_block(body);
+            }
+            NodeData::DoUntil(body, condition) => {
+                self.check_block(body);
+                self.check_condition(condition);
+            }
+            NodeData::Loop(body) => self.check_block(body),
+            NodeData::Repeat(count, body) => {
+                let found = self.infer(count);
+                self.expect(&InferredType::Real, &found, count);
+                self.check_block(body);
+            }
+            NodeData::For(variable, iterable, body) => {
+                let item = self.infer_item(iterable);
+
+                self.scopes.push(HashMap::new());
+                self.declare(variable, item);
+                self.check_block(body);
+                self.scopes.pop();
+            }
+            NodeData::ExprStatement(expr) => {
+                self.infer(expr);
+            }
+            _ => {}
+        }
+    }
+
+    fn check_condition(&mut self, condition: &Node) {
+        let found = self.infer(condition);
+        self.expect(&InferredType::Bool, &found, condition);
+    }
+
+    /// Checks storing a value of type `found` in `target`, `stmt` being the
+    /// whole modification.
+    fn check_assignment(
+        &mut self,
+        stmt: &Node,
+        target: &Node,
+        modification: &VariableModificationType,
+        found: &InferredType,
+        value: &Node,
+    ) {
+        let operator = match modification {
+            VariableModificationType::Set => None,
+            VariableModificationType::IncreaseBy => Some(OperatorType::Add),
+            VariableModificationType::DecreaseBy => Some(OperatorType::Sub),
+            VariableModificationType::MultiplyBy => Some(OperatorType::Mul),
+            VariableModificationType::DivideBy => Some(OperatorType::Div),
+        };
+
+        if let (NodeData::Tuple(targets), None) = (&*target.data, &operator) {
+            let items: Vec<InferredType> = match found {
+                InferredType::Tuple(types) if types.len() == targets.len() => types.clone(),
+                InferredType::Array(inner) => vec![*inner.clone(); targets.len()],
+                InferredType::Unknown => vec![InferredType::Unknown; targets.len()],
+                found => {
+                    let expected = vec![InferredType::Unknown; targets.len()];
+                    self.expect(&InferredType::Tuple(expected), found, value);
+                    return;
+                }
+            };
+
+            for (target, item) in targets.iter().zip(&items) {
+                self.check_assignment(stmt, target, modification, item, value);
+            }
+            return;
+        }
+
+        let expected = self.infer(target);
+        match operator {
+            Some(operator) => {
+                self.binary_result(&operator, &expected, found, stmt);
+            }
+            None => self.expect(&expected, found, value),
+        }
+    }
+
+    /// Type of the items a `for` loop goes through.
+    fn infer_item(&mut self, iterable: &Node) -> InferredType {
+        if let NodeData::Range(start, end, _, step) = &*iterable.data {
+            for bound in [start, end].into_iter().chain(step) {
+                let found = self.infer(bound);
+                self.expect(&InferredType::Real, &found, bound);
+            }
+            return InferredType::Real;
+        }
+
+        match self.infer(iterable) {
+            InferredType::Array(inner) => *inner,
+            InferredType::Tuple(types) => match types.split_first() {
+                Some((first, rest)) if rest.iter().all(|t| t == first) => first.clone(),
+                _ => InferredType::Unknown,
+            },
+            InferredType::Unknown => InferredType::Unknown,
+            found => {
+                self.error(TypeErrorData::NotIterable(found), iterable);
+                InferredType::Unknown
+            }
+        }
+    }
+
+    fn infer(&mut self, node: &Node) -> InferredType {
+        match &*node.data {
+            NodeData::Identifier(name) => match (self.lookup(name), name.as_str()) {
+                (Some(r#type), _) => r#type.clone(),
+                (None, "true" | "false") => InferredType::Bool,
+                (None, _) => InferredType::Unknown,
+            },
+            NodeData::NumericLiteral(_) => InferredType::Real,
+            NodeData::String(_) => InferredType::String,
+            NodeData::Tuple(values) => {
+                InferredType::Tuple(values.iter().map(|value| self.infer(value)).collect())
+            }
+            NodeData::BinaryExpr(a, operator, b) => {
+                let a = self.infer(a);
+                let b = self.infer(b);
+                self.binary_result(operator, &a, &b, node)
+            }
+            NodeData::Neg(value) => self.unary_result("-", InferredType::Real, value, node),
+            NodeData::BitNot(value) => self.unary_result("~", InferredType::Real, value, node),
+            NodeData::Not(value) => self.unary_result("!", InferredType::Bool, value, node),
+            NodeData::FunctionCall(callee, args) => {
+                let callee_type = self.infer(callee);
+                let found: Vec<InferredType> = args.iter().map(|arg| self.infer(arg)).collect();
+
+                match callee_type {
+                    InferredType::Function(params, return_type) => {
+                        if params.len() != args.len() {
+                            self.error(
+                                TypeErrorData::WrongArgumentCount(params.len(), args.len()),
+                                node,
+                            );
+                        } else {
+                            for ((expected, found), arg) in params.iter().zip(&found).zip(args) {
+                                self.expect(expected, found, arg);
+                            }
+                        }
+
+                        *return_type
+                    }
+                    InferredType::Unknown => InferredType::Unknown,
+                    found => {
+                        self.error(TypeErrorData::NotCallable(found), callee);
+                        InferredType::Unknown
+                    }
+                }
+            }
+            NodeData::StructAccess(value, property) | NodeData::ModAccess(value, property) => {
+                self.infer(value);
+                self.infer_member(value, property)
+            }
+            NodeData::ArrayAccess(array, idx) => {
+                let array_type = self.infer(array);
+                let idx_type = self.infer(idx);
+                self.expect(&InferredType::Real, &idx_type, idx);
+
+                match array_type {
+                    InferredType::Array(inner) => *inner,
+                    InferredType::Tuple(types) => match &*idx.data {
+                        NodeData::NumericLiteral(num) => match num.value {
+                            NumericValue::Integer(i) => types
+                                .get(i as usize)
+                                .cloned()
+                                .unwrap_or(InferredType::Unknown),
+                            NumericValue::Real(_) => InferredType::Unknown,
+                        },
+                        _ => InferredType::Unknown,
+                    },
+                    InferredType::Unknown | InferredType::Named(..) => InferredType::Unknown,
+                    found => {
+                        self.error(TypeErrorData::NotIndexable(found), array);
+                        InferredType::Unknown
+                    }
+                }
+            }
+            _ => InferredType::Unknown,
+        }
+    }
+
+    /// Type of `object::member`, for members declared in the project. `value`
+    /// can also be an instance of an object, like `other` in a collision event.
+    fn infer_member(&self, value: &Node, property: &Node) -> InferredType {
+        let (NodeData::Identifier(id), NodeData::Identifier(name)) =
+            (&*value.data, &*property.data)
+        else {
+            return InferredType::Unknown;
+        };
+        let object = match self.lookup(id) {
+            Some(InferredType::Named(object, generics)) if generics.is_empty() => object,
+            Some(_) => return InferredType::Unknown,
+            None => id,
+        };
+
+        match self.info.member(object, name) {
+            Some(Member::Field(field)) => match &field.r#type {
+                Some(r#type) => r#type.into(),
+                None => InferredType::Unknown,
+            },
+            Some(Member::Function(function)) => function.into(),
+            None => InferredType::Unknown,
+        }
+    }
+
+    fn unary_result(
+        &mut self,
+        operator: &str,
+        expected: InferredType,
+        value: &Node,
+        node: &Node,
+    ) -> InferredType {
+        let found = self.infer(value);
+        if !found.is(&expected) {
+            self.error(
+                TypeErrorData::InvalidOperand(operator.to_string(), found),
+                node,
+            );
+        }
+
+        expected
+    }
+
+    /// Type of `a operator b`, reporting `node` when the operator can't take
+    /// those operands.
+    fn binary_result(
+        &mut self,
+        operator: &OperatorType,
+        a: &InferredType,
+        b: &InferredType,
+        node: &Node,
+    ) -> InferredType {
+        let real = a.is(&InferredType::Real) && b.is(&InferredType::Real);
+        let string = a.is(&InferredType::String) && b.is(&InferredType::String);
+
+        let (valid, result) = match operator {
+            OperatorType::Add => match (a, b) {
+                (InferredType::Unknown, InferredType::Unknown) => (true, InferredType::Unknown),
+                _ if real => (true, InferredType::Real),
+                _ if string => (true, InferredType::String),
+                _ => (false, InferredType::Unknown),
+            },
+            OperatorType::Sub
+            | OperatorType::Mul
+            | OperatorType::Div
+            | OperatorType::IntDiv
+            | OperatorType::Mod
+            | OperatorType::ShiftLeft
+            | OperatorType::ShiftRight
+            | OperatorType::BitAnd
+            | OperatorType::BitOr
+            | OperatorType::BitXor => (real, InferredType::Real),
+            OperatorType::LessThan
+            | OperatorType::GreaterThan
+            | OperatorType::LessOrEquals
+            | OperatorType::GreaterOrEquals => (real || string, InferredType::Bool),
+            OperatorType::IsEquals | OperatorType::NotEquals => {
+                (a.accepts(b) || b.accepts(a), InferredType::Bool)
+            }
+            OperatorType::And | OperatorType::Xor | OperatorType::Or => (
+                a.is(&InferredType::Bool) && b.is(&InferredType::Bool),
+                InferredType::Bool,
+            ),
+        };
+
+        if !valid {
+            self.error(
+                TypeErrorData::InvalidOperands(operator.to_string(), a.clone(), b.clone()),
+                node,
+            );
+        }
+
+        result
+    }
+}
+
+fn type_of_node(node: &Node) -> InferredType {
+    match &*node.data {
+        NodeData::Type(r#type) => r#type.into(),
+        _ => InferredType::Unknown,
+    }
+}
+
+/// Type of a declared function, from its parameters and `-> Type`. Without
+/// one, it returns nothing unless its body has a `return value;`.
+fn function_type(params: &[Node], return_type: &Option<Node>, body: &Node) -> InferredType {
+    let params = params
+        .iter()
+        .map(|param| match &*param.data {
+            NodeData::FunctionParemeter(_, Some(r#type)) => type_of_node(r#type),
+            _ => InferredType::Unknown,
+        })
+        .collect();
+    let return_type = match return_type {
+        Some(r#type) => type_of_node(r#type),
+        None if returns_value(body) => InferredType::Unknown,
+        None => InferredType::Void,
+    };
+
+    InferredType::Function(params, Box::new(return_type))
+}
+
+/// Whether the body of a function has a `return value;`, leaving out the
+/// functions declared inside it.
+pub fn returns_value(node: &Node) -> bool {
+    match &*node.data {
+        NodeData::Program(statements) => statements.iter().any(returns_value),
+        NodeData::Return(value) => value.is_some(),
+        NodeData::If(_, body, else_node) => {
+            returns_value(body) || else_node.as_ref().is_some_and(returns_value)
+        }
+        NodeData::Else(body)
+        | NodeData::While(_, body)
+        | NodeData::Loop(body)
+        | NodeData::Repeat(_, body)
+        | NodeData::DoUntil(body, _)
+        | NodeData::For(_, _, body) => returns_value(body),
+        _ => false,
+    }
+}
@@ -1,11 +1,19 @@
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
+pub mod codegen;
 pub mod compiler;
+pub mod event;
+pub mod resource;
+
+use event::GameMakerEvent;
+use resource::{IdeVersion, ResourceFormat};
 
 pub struct GameMakerProject<'a> {
     pub objects: Vec<GameMakerObject<'a>>,
     pub scripts: Vec<GameMakerScript<'a>>,
+    /// Release of the IDE the resources are written for.
+    pub ide_version: IdeVersion,
 }
 
 impl GameMakerProject<'_> {
@@ -15,26 +23,30 @@ impl GameMakerProject<'_> {
             fs::remove_dir_all(&path)?;
         }
         fs::create_dir(&path)?;
+        let format = self.ide_version.resource_format();
 
         let objects_path = path.join("objects");
         fs::create_dir(&objects_path)?;
         for object in &self.objects {
-            object.write_into_fs(&objects_path)?;
+            object.write_into_fs(&objects_path, format)?;
         }
 
         let scripts_path = path.join("scripts");
         fs::create_dir(&scripts_path)?;
         for script in &self.scripts {
-            let script_path = scripts_path.join(&script.name);
-            fs::create_dir(&script_path)?;
-            fs::write(
-                script_path.join(format!("{}.gml", &script.name)),
-                &script.src,
-            )?;
-            fs::write(script_path.join(format!("{}.yy", &script.name)), "")?;
+            script.write_into_fs(&scripts_path, format)?;
         }
 
-        fs::write(path.join("project.yyp"), "{}")?;
+        // GameMaker expects the project file to be named after its directory.
+        let name = path
+            .canonicalize()?
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| "project".to_string());
+        fs::write(
+            path.join(format!("{}.yyp", name)),
+            resource::project_yyp(self, &name),
+        )?;
 
         Ok(())
     }
@@ -42,69 +54,91 @@ impl GameMakerProject<'_> {
 
 pub struct GameMakerObject<'a> {
     pub name: &'a String,
-    pub info: String,
-    pub create: Option<&'a String>,
-    pub step: Option<&'a String>,
-    pub clean_up: Option<&'a String>,
-    pub draw: Option<&'a String>,
-    pub draw_gui: Option<&'a String>,
+    pub properties: ObjectProperties,
+    /// Code of every event the object has, sorted by event.
+    pub events: Vec<(GameMakerEvent, String)>,
+}
+
+/// Settings of an object that go in its `.yy` file.
+#[derive(Debug, Clone)]
+pub struct ObjectProperties {
+    pub sprite: Option<String>,
+    pub mask: Option<String>,
+    /// Object this one inherits from.
+    pub parent: Option<String>,
+    pub visible: bool,
+    pub solid: bool,
+    pub persistent: bool,
+    /// Objects have no depth in the `.yy`, so it is set in the create event.
+    pub depth: Option<i32>,
+}
+
+impl Default for ObjectProperties {
+    fn default() -> Self {
+        ObjectProperties {
+            sprite: None,
+            mask: None,
+            parent: None,
+            visible: true,
+            solid: false,
+            persistent: false,
+            depth: None,
+        }
+    }
 }
 
 impl GameMakerObject<'_> {
-    pub fn new<'a, T: Into<String>>(
+    pub fn new<'a>(
         name: &'a String,
-        info: T,
-        create: Option<&'a String>,
-        step: Option<&'a String>,
-        clean_up: Option<&'a String>,
-        draw: Option<&'a String>,
-        draw_gui: Option<&'a String>,
+        properties: ObjectProperties,
+        mut events: Vec<(GameMakerEvent, String)>,
     ) -> GameMakerObject<'a> {
+        events.sort_by(|(a, _), (b, _)| a.cmp(b));
+
         GameMakerObject {
             name,
-            info: info.into(),
-            create,
-            step,
-            clean_up,
-            draw,
-            draw_gui,
+            properties,
+            events,
         }
     }
 
-    fn create_event_file(
-        &self,
-        path: &PathBuf,
-        src: &Option<&String>,
-        name: &str,
-    ) -> Result<(), std::io::Error> {
-        if let Some(src) = src {
-            fs::write(path.join(name), src)?;
-        }
-
-        Ok(())
-    }
-    pub fn write_into_fs(&self, path: &PathBuf) -> Result<(), std::io::Error> {
-        let path = path.join(&self.name);
+    pub fn write_into_fs(&self, path: &Path, format: ResourceFormat) -> Result<(), std::io::Error> {
+        let path = path.join(self.name);
 
         fs::create_dir(&path)?;
-        self.create_event_file(&path, &self.create, "Create_0.gml")?;
-        self.create_event_file(&path, &self.step, "Step_1.gml")?;
-        self.create_event_file(&path, &self.draw, "Draw_0.gml")?;
-        self.create_event_file(&path, &self.draw_gui, "Draw_64.gml")?;
-        self.create_event_file(&path, &self.clean_up, "CleanUp_0.gml")?;
+        for (event, src) in &self.events {
+            fs::write(path.join(event.file_name()), src)?;
+        }
+        fs::write(
+            path.join(format!("{}.yy", self.name)),
+            resource::object_yy(self, format),
+        )?;
 
         Ok(())
     }
 }
 
+/// A script asset, compiled from one source file of the `scripts` directory.
 pub struct GameMakerScript<'a> {
     pub name: &'a String,
-    pub src: &'a String,
-    pub info: String,
+    pub src: String,
 }
 
 impl<'a> GameMakerScript<'a> {
-    pub fn new(name: &'a String, src: &'a String, info: String) -> GameMakerScript<'a> {
-        GameMakerScript { name, src, info }
+    pub fn new(name: &'a String, src: String) -> GameMakerScript<'a> {
+        GameMakerScript { name, src }
+    }
+
+    pub fn write_into_fs(&self, path: &Path, format: ResourceFormat) -> Result<(), std::io::Error> {
+        let path = path.join(self.name);
+
+        fs::create_dir(&path)?;
+        fs::write(path.join(format!("{}.gml", self.name)), &self.src)?;
+        fs::write(
+            path.join(format!("{}.yy", self.name)),
+            resource::script_yy(self, format),
+        )?;
+
+        Ok(())
     }
 }
@@ -0,0 +1,467 @@
+use crate::{
+    ast::{DeclarationType, DocComment, Node, NodeData, OperatorType, RangeKind},
+    parser::types::YoyogayType,
+};
+
+const INDENT: &str = "    ";
+
+pub fn generate_gml(program: &Node) -> String {
+    let mut generator = GmlGenerator::new();
+    generator.gen_stmt(program);
+
+    generator.out
+}
+
+/// Like `generate_gml`, for a script. Its top-level constants become macros so
+/// objects can use them too, which means their value is computed where they are
+/// used.
+pub fn generate_script_gml(program: &Node) -> String {
+    let mut generator = GmlGenerator::new();
+    let NodeData::Program(statements) = &*program.data else {
+        generator.gen_stmt(program);
+        return generator.out;
+    };
+
+    for stmt in statements {
+        match &*stmt.data {
+            NodeData::VariableDeclaration(DeclarationType::Const, id, _, value, doc) => {
+                generator.gen_doc_comment(doc, &[], None);
+                generator.line(format!("#macro {} ({})", gen_expr(id), gen_expr(value)));
+            }
+            _ => generator.gen_stmt(stmt),
+        }
+    }
+
+    generator.out
+}
+
+struct GmlGenerator {
+    out: String,
+    indent: usize,
+    temp_count: usize,
+}
+
+impl GmlGenerator {
+    fn new() -> Self {
+        GmlGenerator {
+            out: String::new(),
+            indent: 0,
+            temp_count: 0,
+        }
+    }
+
+    fn line<T: AsRef<str>>(&mut self, txt: T) {
+        for _ in 0..self.indent {
+            self.out.push_str(INDENT);
+        }
+        self.out.push_str(txt.as_ref());
+        self.out.push('\n');
+    }
+
+    fn new_temp(&mut self, name: &str) -> String {
+        let temp = format!("__yyg_{}_{}", name, self.temp_count);
+        self.temp_count += 1;
+
+        temp
+    }
+
+    /// Stores `node` in a temporary unless it is a literal, so it is evaluated once.
+    fn cached(&mut self, node: &Node, name: &str) -> String {
+        if is_literal(node) {
+            return gen_expr(node);
+        }
+
+        let temp = self.new_temp(name);
+        self.line(format!("var {} = {};", temp, gen_expr(node)));
+
+        temp
+    }
+
+    fn gen_range_for(&mut self, variable: &Node, range: &Node, body: &Node) {
+        let NodeData::Range(start, end, kind, step) = &*range.data else {
+            return;
+        };
+
+        let end = self.cached(end, "end");
+        let step = step.as_ref().map(|step| (self.cached(step, "step"), step));
+        let variable = gen_expr(variable);
+
+        let (ascending, descending) = match kind {
+            RangeKind::Exclusive => ("<", ">"),
+            RangeKind::Inclusive => ("<=", ">="),
+        };
+        let condition = match &step {
+            None => format!("{} {} {}", variable, ascending, end),
+            Some((_, node)) if is_literal(node) => {
+                let comparison = match &*node.data {
+                    NodeData::Neg(_) => descending,
+                    _ => ascending,
+                };
+                format!("{} {} {}", variable, comparison, end)
+            }
+            // The sign of the step is only known when the loop runs.
+            Some((step, _)) => format!(
+                "({} > 0 ? {} {} {} : {} {} {})",
+                step, variable, ascending, end, variable, descending, end
+            ),
+        };
+        let increment = match &step {
+            Some((step, _)) => format!("{} += {}", variable, step),
+            None => format!("{}++", variable),
+        };
+
+        self.line(format!(
+            "for (var {} = {}; {}; {}) {{",
+            variable,
+            gen_expr(start),
+            condition,
+            increment
+        ));
+        self.gen_body(body);
+        self.line("}");
+    }
+
+    fn gen_array_for(&mut self, variable: &Node, array: &Node, body: &Node) {
+        let array = match &*array.data {
+            NodeData::Identifier(id) => id.to_string(),
+            _ => self.cached(array, "array"),
+        };
+        let length = self.new_temp("length");
+        self.line(format!("var {} = array_length({});", length, array));
+
+        let idx = self.new_temp("i");
+        self.line(format!(
+            "for (var {} = 0; {} < {}; {}++) {{",
+            idx, idx, length, idx
+        ));
+        self.indent += 1;
+        self.line(format!("var {} = {}[{}];", gen_expr(variable), array, idx));
+        self.gen_stmt(body);
+        self.indent -= 1;
+        self.line("}");
+    }
+
+    fn gen_body(&mut self, body: &Node) {
+        self.indent += 1;
+        self.gen_stmt(body);
+        self.indent -= 1;
+    }
+
+    /// Turns `///` comments into Feather headers. Plain lines become the
+    /// `@description`, tag lines are kept and every parameter gets an `@param`.
+    /// Annotated types fill in the `@param` and `@return` tags that are missing.
+    fn gen_doc_comment(
+        &mut self,
+        doc: &DocComment,
+        params: &[(String, Option<String>)],
+        return_type: Option<String>,
+    ) {
+        if doc.is_empty() {
+            return;
+        }
+
+        let (tags, description): (Vec<&String>, Vec<&String>) = doc
+            .iter()
+            .copied()
+            .partition(|line| line.trim_start().starts_with('@'));
+        let description: Vec<&str> = description.iter().map(|line| line.trim()).collect();
+        let description = description.join(" ");
+
+        if !description.trim().is_empty() {
+            self.line(format!("/// @description {}", description.trim()));
+        }
+
+        for (param, r#type) in params {
+            let tag = tags
+                .iter()
+                .find(|tag| doc_param_name(tag) == Some(param.as_str()));
+            match (tag, r#type) {
+                (Some(tag), _) => self.line(format!("/// {}", tag.trim())),
+                (None, Some(r#type)) => self.line(format!("/// @param {{{}}} {}", r#type, param)),
+                (None, None) => self.line(format!("/// @param {}", param)),
+            }
+        }
+
+        let has_return_tag = tags
+            .iter()
+            .any(|tag| tag.trim_start().starts_with("@return"));
+        if let (Some(return_type), false) = (return_type, has_return_tag) {
+            self.line(format!("/// @return {{{}}}", return_type));
+        }
+
+        for tag in tags {
+            let is_param =
+                doc_param_name(tag).is_some_and(|name| params.iter().any(|(p, _)| p == name));
+            if !is_param {
+                self.line(format!("/// {}", tag.trim()));
+            }
+        }
+    }
+
+    fn gen_stmt(&mut self, node: &Node) {
+        match &*node.data {
+            NodeData::Program(statements) => {
+                for stmt in statements {
+                    self.gen_stmt(stmt);
+                }
+            }
+            NodeData::VariableDeclaration(declaration_type, id, _, value, doc) => {
+                self.gen_doc_comment(doc, &[], None);
+                let txt = match declaration_type {
+                    // Instance variables are created by assigning to them.
+                    DeclarationType::Var => format!("{} = {};", gen_expr(id), gen_expr(value)),
+                    DeclarationType::Let | DeclarationType::Const => {
+                        format!("var {} = {};", gen_expr(id), gen_expr(value))
+                    }
+                };
+                self.line(txt);
+            }
+            NodeData::VariableModification(target, mod_type, value) => {
+                if let NodeData::Tuple(targets) = &*target.data {
+                    let temp = self.new_temp("tuple");
+                    self.line(format!("var {} = {};", temp, gen_expr(value)));
+                    for (i, target) in targets.iter().enumerate() {
+                        let txt = format!("{} {} {}[{}];", gen_expr(target), mod_type, temp, i);
+                        self.line(txt);
+                    }
+                } else {
+                    let txt = format!("{} {} {};", gen_expr(target), mod_type, gen_expr(value));
+                    self.line(txt);
+                }
+            }
+            NodeData::FunctionDeclaration(name, params, return_type, body, doc) => {
+                let typed_params: Vec<(String, Option<String>)> = params
+                    .iter()
+                    .map(|param| match &*param.data {
+                        NodeData::FunctionParemeter(id, r#type) => {
+                            (gen_expr(id), r#type.as_ref().map(feather_type))
+                        }
+                        _ => (gen_expr(param), None),
+                    })
+                    .collect();
+                self.gen_doc_comment(doc, &typed_params, return_type.as_ref().map(feather_type));
+
+                let params: Vec<String> = typed_params.into_iter().map(|(name, _)| name).collect();
+                self.line(format!(
+                    "function {}({}) {{",
+                    gen_expr(name),
+                    params.join(", ")
+                ));
+                self.gen_body(body);
+                self.line("}");
+            }
+            NodeData::If(condition, body, else_node) => {
+                self.line(format!("if ({}) {{", gen_expr(condition)));
+                self.gen_body(body);
+
+                // `else if` chains stay flat instead of nesting an `if` in each `else`.
+                let mut else_node = else_node.as_ref();
+                while let Some(node) = else_node {
+                    match &*node.data {
+                        NodeData::If(condition, body, next) => {
+                            self.line(format!("}} else if ({}) {{", gen_expr(condition)));
+                            self.gen_body(body);
+                            else_node = next.as_ref();
+                        }
+                        NodeData::Else(else_body) => {
+                            self.line("} else {");
+                            self.gen_body(else_body);
+                            else_node = None;
+                        }
+                        _ => {
+                            self.line("} else {");
+                            self.gen_body(node);
+                            else_node = None;
+                        }
+                    }
+                }
+
+                self.line("}");
+            }
+            NodeData::While(condition, body) => {
+                self.line(format!("while ({}) {{", gen_expr(condition)));
+                self.gen_body(body);
+                self.line("}");
+            }
+            NodeData::Loop(body) => {
+                self.line("while (true) {");
+                self.gen_body(body);
+                self.line("}");
+            }
+            NodeData::Repeat(count, body) => {
+                self.line(format!("repeat ({}) {{", gen_expr(count)));
+                self.gen_body(body);
+                self.line("}");
+            }
+            NodeData::DoUntil(body, condition) => {
+                self.line("do {");
+                self.gen_body(body);
+                self.line(format!("}} until ({});", gen_expr(condition)));
+            }
+            NodeData::For(variable, iterable, body) => match &*iterable.data {
+                NodeData::Range(..) => self.gen_range_for(variable, iterable, body),
+                _ => self.gen_array_for(variable, iterable, body),
+            },
+            NodeData::Return(Some(value)) => {
+                let txt = format!("return {};", gen_expr(value));
+                self.line(txt);
+            }
+            NodeData::Return(None) => self.line("return;"),
+            NodeData::Break => self.line("break;"),
+            NodeData::Continue => self.line("continue;"),
+            NodeData::Super => self.line("event_inherited();"),
+            NodeData::ExprStatement(expr) => {
+                let txt = format!("{};", gen_expr(expr));
+                self.line(txt);
+            }
+            // Programs with errors are never compiled, nothing to emit.
+            NodeData::Error => {}
+            _ => {
+                let txt = format!("{};", gen_expr(node));
+                self.line(txt);
+            }
+        }
+    }
+}
+
+/// Feather spelling of an annotated `Type` node. Tuples are plain arrays in GML.
+fn feather_type(node: &Node) -> String {
+    fn spell(r#type: &YoyogayType) -> String {
+        match r#type {
+            YoyogayType::Identifier(id, generics) if generics.is_empty() => id.to_string(),
+            YoyogayType::Identifier(id, generics) => {
+                let generics: Vec<String> = generics.iter().map(spell).collect();
+                format!("{}<{}>", id, generics.join(", "))
+            }
+            YoyogayType::Array(inner) => format!("Array<{}>", spell(inner)),
+            YoyogayType::Tuple(_) => "Array".to_string(),
+        }
+    }
+
+    match &*node.data {
+        NodeData::Type(r#type) => spell(r#type),
+        _ => gen_expr(node),
+    }
+}
+
+fn is_literal(node: &Node) -> bool {
+    match &*node.data {
+        NodeData::NumericLiteral(_) | NodeData::String(_) => true,
+        NodeData::Neg(value) => is_literal(value),
+        _ => false,
+    }
+}
+
+/// Name documented by an `@param [{Type}] name ...` tag.
+fn doc_param_name(tag: &str) -> Option<&str> {
+    let rest = tag.trim_start().strip_prefix("@param")?.trim_start();
+    let rest = match rest.strip_prefix('{') {
+        Some(typed) => typed.split_once('}')?.1.trim_start(),
+        None => rest,
+    };
+
+    rest.split_whitespace().next()
+}
+
+fn escape_gml_string(txt: &str) -> String {
+    let mut escaped = "\"".to_string();
+
+    for chr in txt.chars() {
+        match chr {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            // GML reads up to six hex digits after `\u`, so pad to six to keep the
+            // following characters out of the escape.
+            _ if chr.is_control() => escaped.push_str(&format!("\\u{:06x}", chr as u32)),
+            _ => escaped.push(chr),
+        }
+    }
+
+    escaped.push('"');
+    escaped
+}
+
+/// GML only orders logical, comparison and bitwise operators by group, so
+/// different operators from those groups are always parenthesized when nested.
+fn is_grouped_in_gml(op: &OperatorType) -> bool {
+    !matches!(
+        op,
+        OperatorType::Add
+            | OperatorType::Sub
+            | OperatorType::Mul
+            | OperatorType::Div
+            | OperatorType::IntDiv
+            | OperatorType::Mod
+            | OperatorType::ShiftLeft
+            | OperatorType::ShiftRight
+    )
+}
+
+fn gen_operand(node: &Node, parent_op: &OperatorType, is_right: bool) -> String {
+    let txt = gen_expr(node);
+
+    if let NodeData::BinaryExpr(_, op, _) = &*node.data {
+        let (precedence, parent_precedence) = (op.precedence(), parent_op.precedence());
+        let is_ambiguous =
+            *op != parent_op && is_grouped_in_gml(op) && is_grouped_in_gml(parent_op);
+
+        if precedence < parent_precedence
+            || (is_right && precedence == parent_precedence)
+            || is_ambiguous
+        {
+            return format!("({})", txt);
+        }
+    }
+
+    txt
+}
+
+fn gen_unary(symbol: &str, expr: &Node) -> String {
+    match &*expr.data {
+        NodeData::BinaryExpr(..) | NodeData::Neg(_) | NodeData::Not(_) | NodeData::BitNot(_) => {
+            format!("{}({})", symbol, gen_expr(expr))
+        }
+        _ => format!("{}{}", symbol, gen_expr(expr)),
+    }
+}
+
+pub fn gen_expr(node: &Node) -> String {
+    match &*node.data {
+        NodeData::Identifier(id) => id.to_string(),
+        // Digit separators are only there for readability, GML gets the bare digits.
+        NodeData::NumericLiteral(num) => num.spelling.replace('_', ""),
+        NodeData::String(txt) => escape_gml_string(txt),
+        NodeData::BinaryExpr(left, op, right) => {
+            format!(
+                "{} {} {}",
+                gen_operand(left, op, false),
+                op,
+                gen_operand(right, op, true)
+            )
+        }
+        NodeData::Neg(expr) => gen_unary("-", expr),
+        NodeData::Not(expr) => gen_unary("!", expr),
+        NodeData::BitNot(expr) => gen_unary("~", expr),
+        NodeData::Tuple(values) => {
+            let values: Vec<String> = values.iter().map(gen_expr).collect();
+            format!("[{}]", values.join(", "))
+        }
+        NodeData::FunctionCall(func, args) => {
+            let args: Vec<String> = args.iter().map(gen_expr).collect();
+            format!("{}({})", gen_expr(func), args.join(", "))
+        }
+        NodeData::FunctionParemeter(param, _) => gen_expr(param),
+        NodeData::StructAccess(struct_node, prop) | NodeData::ModAccess(struct_node, prop) => {
+            format!("{}.{}", gen_expr(struct_node), gen_expr(prop))
+        }
+        NodeData::ArrayAccess(arr_node, idx_node) => {
+            format!("{}[{}]", gen_expr(arr_node), gen_expr(idx_node))
+        }
+        // Statements and ranges never parse as expressions, and programs with
+        // errors are never compiled. `undefined` keeps the output valid GML.
+        _ => "undefined".to_string(),
+    }
+}
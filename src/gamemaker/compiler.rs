@@ -1,28 +1,95 @@
-use crate::organizer::YoyogayProject;
+use crate::{
+    organizer::{source::Source, CheckedProject},
+    parser::ParseError,
+};
 
-use super::{GameMakerObject, GameMakerProject, GameMakerScript};
+use super::{
+    codegen::{generate_gml, generate_script_gml},
+    event::GameMakerEvent,
+    resource::IdeVersion,
+    GameMakerObject, GameMakerProject, GameMakerScript,
+};
 
 impl GameMakerProject<'_> {
     pub fn new_from_yoyogay_project<'a>(
-        yoyogay_project: &'a YoyogayProject,
-    ) -> GameMakerProject<'a> {
+        yoyogay_project: &CheckedProject<'a>,
+    ) -> Result<GameMakerProject<'a>, Vec<ParseError>> {
+        let mut errors: Vec<ParseError> = Vec::new();
         let objects: Vec<GameMakerObject<'a>> = yoyogay_project
             .objects
             .iter()
-            .map(|obj| {
-                GameMakerObject::new(
-                    &obj.id,
-                    "{}",
-                    obj.create.as_ref(),
-                    obj.step.as_ref(),
-                    obj.draw.as_ref(),
-                    obj.draw_gui.as_ref(),
-                    obj.clean_up.as_ref(),
-                )
+            .map(|parsed| {
+                let obj = parsed.object;
+                let mut events: Vec<(GameMakerEvent, String)> = parsed
+                    .events
+                    .iter()
+                    .map(|parsed_event| {
+                        let src = compile_source(&parsed_event.source, &mut errors);
+                        (parsed_event.event.event.clone(), src)
+                    })
+                    .collect();
+
+                if let Some(depth) = obj.properties.depth {
+                    let depth = format!("depth = {};\n", depth);
+                    match events
+                        .iter_mut()
+                        .find(|(event, _)| *event == GameMakerEvent::CREATE)
+                    {
+                        Some((_, src)) => insert_depth(src, &depth),
+                        // A new create event would replace the parent's one.
+                        None if obj.properties.parent.is_some() => events.push((
+                            GameMakerEvent::CREATE,
+                            format!("event_inherited();\n{}", depth),
+                        )),
+                        None => events.push((GameMakerEvent::CREATE, depth)),
+                    }
+                }
+
+                GameMakerObject::new(&obj.id, obj.properties.clone(), events)
             })
             .collect();
-        let scripts: Vec<GameMakerScript<'a>> = Vec::new();
+        let scripts: Vec<GameMakerScript<'a>> = yoyogay_project
+            .scripts
+            .iter()
+            .map(|parsed| {
+                errors.extend(parsed.source.parse_errors.iter().cloned());
+                let src = generate_script_gml(&parsed.source.program);
+
+                GameMakerScript::new(&parsed.script.id, src)
+            })
+            .collect();
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        Ok(GameMakerProject {
+            objects,
+            scripts,
+            ide_version: IdeVersion::default(),
+        })
+    }
+}
+
+fn compile_source(source: &Source, errors: &mut Vec<ParseError>) -> String {
+    errors.extend(source.parse_errors.iter().cloned());
+
+    generate_gml(&source.program)
+}
+
+/// Adds the `depth` assignment to a create event. It goes right after a
+/// top-level `super();` so the parent's create can't override it, at the end
+/// if the parent is only called from a nested block, and first otherwise.
+fn insert_depth(src: &mut String, depth: &str) {
+    const INHERITED: &str = "event_inherited();\n";
 
-        GameMakerProject { objects, scripts }
+    let top_level = src
+        .match_indices(INHERITED)
+        .find(|(idx, _)| *idx == 0 || src[..*idx].ends_with('\n'))
+        .map(|(idx, _)| idx + INHERITED.len());
+    match top_level {
+        Some(idx) => src.insert_str(idx, depth),
+        None if src.contains(INHERITED) => src.push_str(depth),
+        None => src.insert_str(0, depth),
     }
 }
@@ -0,0 +1,248 @@
+use super::resource::is_asset_name;
+
+/// An event of an object, identified the way the `.yy` file does.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct GameMakerEvent {
+    pub event_type: u32,
+    pub event_num: u32,
+    /// Object a collision event is with.
+    pub collision_object: Option<String>,
+}
+
+const COLLISION_EVENT_TYPE: u32 = 4;
+
+/// Prefix of the GML file of every type of event, as in `Alarm_3.gml`.
+const EVENT_TYPES: &[(u32, &str)] = &[
+    (0, "Create"),
+    (1, "Destroy"),
+    (2, "Alarm"),
+    (3, "Step"),
+    (4, "Collision"),
+    (5, "Keyboard"),
+    (6, "Mouse"),
+    (7, "Other"),
+    (8, "Draw"),
+    (9, "KeyPress"),
+    (10, "KeyRelease"),
+    (12, "CleanUp"),
+];
+
+/// Events without arguments, as written after `#event`, with their
+/// `eventType` and `eventNum`.
+const EVENTS: &[(&str, u32, u32)] = &[
+    ("create", 0, 0),
+    ("destroy", 1, 0),
+    ("step", 3, 0),
+    ("begin_step", 3, 1),
+    ("end_step", 3, 2),
+    ("outside_room", 7, 0),
+    ("intersect_boundary", 7, 1),
+    ("game_start", 7, 2),
+    ("game_end", 7, 3),
+    ("room_start", 7, 4),
+    ("room_end", 7, 5),
+    ("animation_end", 7, 7),
+    ("path_ended", 7, 8),
+    ("async_image_loaded", 7, 60),
+    ("async_http", 7, 62),
+    ("async_dialog", 7, 63),
+    ("async_in_app_purchase", 7, 66),
+    ("async_cloud", 7, 67),
+    ("async_networking", 7, 68),
+    ("async_steam", 7, 69),
+    ("async_social", 7, 70),
+    ("async_push_notification", 7, 71),
+    ("async_save_load", 7, 72),
+    ("async_audio_recording", 7, 73),
+    ("async_audio_playback", 7, 74),
+    ("async_system", 7, 75),
+    ("broadcast_message", 7, 76),
+    ("draw", 8, 0),
+    ("draw_gui", 8, 64),
+    ("window_resize", 8, 65),
+    ("draw_begin", 8, 72),
+    ("draw_end", 8, 73),
+    ("draw_gui_begin", 8, 74),
+    ("draw_gui_end", 8, 75),
+    ("pre_draw", 8, 76),
+    ("post_draw", 8, 77),
+    ("clean_up", 12, 0),
+];
+
+/// Events written with an index, like `alarm[3]`: `eventType`, the
+/// `eventNum` of index 0 and how many there are.
+const INDEXED_EVENTS: &[(&str, u32, u32, u32)] = &[("alarm", 2, 0, 12), ("user_event", 7, 10, 16)];
+
+/// Events taking a key, like `key_press vk_space`, and their `eventType`.
+const KEY_EVENTS: &[(&str, u32)] = &[("keyboard", 5), ("key_press", 9), ("key_release", 10)];
+
+/// Buttons of `mouse` events, like `mouse left_pressed`, and their `eventNum`.
+const MOUSE_EVENTS: &[(&str, u32)] = &[
+    ("left_button", 0),
+    ("right_button", 1),
+    ("middle_button", 2),
+    ("no_button", 3),
+    ("left_pressed", 4),
+    ("right_pressed", 5),
+    ("middle_pressed", 6),
+    ("left_released", 7),
+    ("right_released", 8),
+    ("middle_released", 9),
+    ("mouse_enter", 10),
+    ("mouse_leave", 11),
+    ("global_left_button", 50),
+    ("global_right_button", 51),
+    ("global_middle_button", 52),
+    ("global_left_pressed", 53),
+    ("global_right_pressed", 54),
+    ("global_middle_pressed", 55),
+    ("global_left_released", 56),
+    ("global_right_released", 57),
+    ("global_middle_released", 58),
+    ("wheel_up", 60),
+    ("wheel_down", 61),
+];
+
+/// Key codes of the `vk_` constants. Letters and digits use their ASCII code.
+const KEYS: &[(&str, u32)] = &[
+    ("vk_nokey", 0),
+    ("vk_anykey", 1),
+    ("vk_backspace", 8),
+    ("vk_tab", 9),
+    ("vk_enter", 13),
+    ("vk_shift", 16),
+    ("vk_control", 17),
+    ("vk_alt", 18),
+    ("vk_pause", 19),
+    ("vk_escape", 27),
+    ("vk_space", 32),
+    ("vk_pageup", 33),
+    ("vk_pagedown", 34),
+    ("vk_end", 35),
+    ("vk_home", 36),
+    ("vk_left", 37),
+    ("vk_up", 38),
+    ("vk_right", 39),
+    ("vk_down", 40),
+    ("vk_printscreen", 44),
+    ("vk_insert", 45),
+    ("vk_delete", 46),
+    ("vk_numpad0", 96),
+    ("vk_numpad1", 97),
+    ("vk_numpad2", 98),
+    ("vk_numpad3", 99),
+    ("vk_numpad4", 100),
+    ("vk_numpad5", 101),
+    ("vk_numpad6", 102),
+    ("vk_numpad7", 103),
+    ("vk_numpad8", 104),
+    ("vk_numpad9", 105),
+    ("vk_multiply", 106),
+    ("vk_add", 107),
+    ("vk_subtract", 109),
+    ("vk_decimal", 110),
+    ("vk_divide", 111),
+    ("vk_f1", 112),
+    ("vk_f2", 113),
+    ("vk_f3", 114),
+    ("vk_f4", 115),
+    ("vk_f5", 116),
+    ("vk_f6", 117),
+    ("vk_f7", 118),
+    ("vk_f8", 119),
+    ("vk_f9", 120),
+    ("vk_f10", 121),
+    ("vk_f11", 122),
+    ("vk_f12", 123),
+    ("vk_lshift", 160),
+    ("vk_rshift", 161),
+    ("vk_lcontrol", 162),
+    ("vk_rcontrol", 163),
+    ("vk_lalt", 164),
+    ("vk_ralt", 165),
+];
+
+impl GameMakerEvent {
+    pub const CREATE: GameMakerEvent = GameMakerEvent::new(0, 0);
+
+    pub const fn new(event_type: u32, event_num: u32) -> GameMakerEvent {
+        GameMakerEvent {
+            event_type,
+            event_num,
+            collision_object: None,
+        }
+    }
+
+    pub fn collision(object: &str) -> GameMakerEvent {
+        GameMakerEvent {
+            collision_object: Some(object.to_string()),
+            ..GameMakerEvent::new(COLLISION_EVENT_TYPE, 0)
+        }
+    }
+
+    /// The event named by the text after `#event`, like `step`, `alarm[3]`,
+    /// `key_press vk_space`, `mouse left_pressed` or `collision obj_enemy`.
+    pub fn from_name(name: &str) -> Option<GameMakerEvent> {
+        let name = name.trim();
+
+        if let Some((name, argument)) = name.split_once(char::is_whitespace) {
+            let argument = argument.trim();
+            if name == "collision" {
+                return is_asset_name(argument).then(|| GameMakerEvent::collision(argument));
+            }
+            if name == "mouse" {
+                let (_, event_num) = MOUSE_EVENTS
+                    .iter()
+                    .find(|(button, _)| *button == argument)?;
+                return Some(GameMakerEvent::new(6, *event_num));
+            }
+
+            let (_, event_type) = KEY_EVENTS.iter().find(|(event, _)| *event == name)?;
+            return Some(GameMakerEvent::new(*event_type, key_code(argument)?));
+        }
+
+        if let Some((name, index)) = name.strip_suffix(']').and_then(|name| name.split_once('[')) {
+            let (_, event_type, first, count) =
+                INDEXED_EVENTS.iter().find(|(event, ..)| *event == name)?;
+            let index: u32 = index.trim().parse().ok()?;
+            if index >= *count {
+                return None;
+            }
+            return Some(GameMakerEvent::new(*event_type, first + index));
+        }
+
+        EVENTS
+            .iter()
+            .find(|(event, ..)| *event == name)
+            .map(|(_, event_type, event_num)| GameMakerEvent::new(*event_type, *event_num))
+    }
+
+    /// Name of the GML file holding the code of the event.
+    pub fn file_name(&self) -> String {
+        if let Some(object) = &self.collision_object {
+            return format!("Collision_{}.gml", object);
+        }
+
+        let prefix = EVENT_TYPES
+            .iter()
+            .find(|(event_type, _)| *event_type == self.event_type)
+            .map(|(_, prefix)| *prefix)
+            .unwrap_or("Other");
+
+        format!("{}_{}.gml", prefix, self.event_num)
+    }
+}
+
+/// Code of a key written as a `vk_` constant, a letter, a digit or a number.
+fn key_code(key: &str) -> Option<u32> {
+    if let Some((_, code)) = KEYS.iter().find(|(name, _)| *name == key) {
+        return Some(*code);
+    }
+
+    let mut chars = key.chars();
+    match (chars.next(), chars.next()) {
+        (Some(ch), None) if ch.is_ascii_alphabetic() => Some(ch.to_ascii_uppercase() as u32),
+        (Some(ch), None) if ch.is_ascii_digit() => Some(ch as u32),
+        _ => key.parse().ok().filter(|code| *code < 256),
+    }
+}
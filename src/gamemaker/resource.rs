@@ -0,0 +1,490 @@
+use std::{fmt, str::FromStr};
+
+use super::{GameMakerObject, GameMakerProject, GameMakerScript};
+
+/// Folder of the asset browser every generated object is placed in.
+pub const OBJECTS_FOLDER: &str = "Objects";
+/// Folder of the asset browser every generated script is placed in.
+pub const SCRIPTS_FOLDER: &str = "Scripts";
+
+/// Release of the GameMaker IDE the project is generated for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct IdeVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+    pub build: u32,
+}
+
+impl IdeVersion {
+    pub const fn new(major: u32, minor: u32, patch: u32, build: u32) -> IdeVersion {
+        IdeVersion {
+            major,
+            minor,
+            patch,
+            build,
+        }
+    }
+
+    /// GameMaker changed how resources are written in 2023.8.
+    pub fn resource_format(&self) -> ResourceFormat {
+        if (self.major, self.minor) >= (2023, 8) {
+            ResourceFormat::Modern
+        } else {
+            ResourceFormat::Legacy
+        }
+    }
+}
+
+impl Default for IdeVersion {
+    fn default() -> Self {
+        IdeVersion::new(2024, 2, 0, 163)
+    }
+}
+
+impl fmt::Display for IdeVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let txt = format!(
+            "{}.{}.{}.{}",
+            self.major, self.minor, self.patch, self.build
+        );
+
+        f.write_str(&txt)
+    }
+}
+
+#[derive(Debug)]
+pub struct InvalidIdeVersion(pub String);
+
+impl FromStr for IdeVersion {
+    type Err = InvalidIdeVersion;
+
+    /// Parses versions like `2024.2.0.163`. Missing trailing parts are 0.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.split('.').collect();
+        if parts.len() > 4 {
+            return Err(InvalidIdeVersion(s.to_string()));
+        }
+
+        let mut numbers = [0; 4];
+        for (number, part) in numbers.iter_mut().zip(parts) {
+            *number = part.parse().map_err(|_| InvalidIdeVersion(s.to_string()))?;
+        }
+
+        let [major, minor, patch, build] = numbers;
+        Ok(IdeVersion::new(major, minor, patch, build))
+    }
+}
+
+/// How `.yy` and `.yyp` files are laid out.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ResourceFormat {
+    /// GameMaker 2.3 up to 2023.6: `"key": value` and `resourceVersion` 1.x.
+    Legacy,
+    /// GameMaker 2023.8 onwards: `"key":value` with sorted keys, a `$GMType`
+    /// header and `resourceVersion` 2.0.
+    Modern,
+}
+
+/// The `.yy` resource of an object. Both formats indent by two spaces and put
+/// a comma after every value.
+pub fn object_yy(object: &GameMakerObject, format: ResourceFormat) -> String {
+    let properties = &object.properties;
+    let events: Vec<String> = object
+        .events
+        .iter()
+        .map(|(event, _)| (event, collision_ref(event.collision_object.as_deref())))
+        .map(|(event, collision_object)| match format {
+            ResourceFormat::Modern => inline_object(&[
+                ("$GMEvent", string("v1")),
+                ("%Name", string("")),
+                ("collisionObjectId", collision_object),
+                ("eventNum", event.event_num.to_string()),
+                ("eventType", event.event_type.to_string()),
+                ("isDnD", "false".to_string()),
+                ("name", string("")),
+                ("resourceType", string("GMEvent")),
+                ("resourceVersion", string("2.0")),
+            ]),
+            ResourceFormat::Legacy => inline_object(&[
+                ("isDnD", "false".to_string()),
+                ("eventNum", event.event_num.to_string()),
+                ("eventType", event.event_type.to_string()),
+                ("collisionObjectId", collision_object),
+                ("resourceVersion", string("1.0")),
+                ("name", string("")),
+                ("tags", "[]".to_string()),
+                ("resourceType", string("GMEvent")),
+            ]),
+        })
+        .collect();
+
+    let parent_object = resource_ref("objects", properties.parent.as_deref(), 1, format);
+    let sprite = resource_ref("sprites", properties.sprite.as_deref(), 1, format);
+    let mask = resource_ref("sprites", properties.mask.as_deref(), 1, format);
+    let folder = folder_ref(OBJECTS_FOLDER, 1, format);
+
+    let fields = match format {
+        ResourceFormat::Modern => vec![
+            ("$GMObject", string("")),
+            ("%Name", string(object.name)),
+            ("eventList", array(&events, 1)),
+            ("managed", "true".to_string()),
+            ("name", string(object.name)),
+            ("overriddenProperties", "[]".to_string()),
+            ("parent", folder),
+            ("parentObjectId", parent_object),
+            ("persistent", properties.persistent.to_string()),
+            ("physicsAngularDamping", "0.1".to_string()),
+            ("physicsDensity", "0.5".to_string()),
+            ("physicsFriction", "0.2".to_string()),
+            ("physicsGroup", "1".to_string()),
+            ("physicsKinematic", "false".to_string()),
+            ("physicsLinearDamping", "0.1".to_string()),
+            ("physicsObject", "false".to_string()),
+            ("physicsRestitution", "0.1".to_string()),
+            ("physicsSensor", "false".to_string()),
+            ("physicsShape", "1".to_string()),
+            ("physicsShapePoints", "[]".to_string()),
+            ("physicsStartAwake", "true".to_string()),
+            ("properties", "[]".to_string()),
+            ("resourceType", string("GMObject")),
+            ("resourceVersion", string("2.0")),
+            ("solid", properties.solid.to_string()),
+            ("spriteId", sprite),
+            ("spriteMaskId", mask),
+            ("visible", properties.visible.to_string()),
+        ],
+        ResourceFormat::Legacy => vec![
+            ("spriteId", sprite),
+            ("solid", properties.solid.to_string()),
+            ("visible", properties.visible.to_string()),
+            ("managed", "true".to_string()),
+            ("spriteMaskId", mask),
+            ("persistent", properties.persistent.to_string()),
+            ("parentObjectId", parent_object),
+            ("physicsObject", "false".to_string()),
+            ("physicsSensor", "false".to_string()),
+            ("physicsShape", "1".to_string()),
+            ("physicsGroup", "1".to_string()),
+            ("physicsDensity", "0.5".to_string()),
+            ("physicsRestitution", "0.1".to_string()),
+            ("physicsLinearDamping", "0.1".to_string()),
+            ("physicsAngularDamping", "0.1".to_string()),
+            ("physicsFriction", "0.2".to_string()),
+            ("physicsStartAwake", "true".to_string()),
+            ("physicsKinematic", "false".to_string()),
+            ("physicsShapePoints", "[]".to_string()),
+            ("eventList", array(&events, 1)),
+            ("properties", "[]".to_string()),
+            ("overriddenProperties", "[]".to_string()),
+            ("parent", folder),
+            ("resourceVersion", string("1.0")),
+            ("name", string(object.name)),
+            ("tags", "[]".to_string()),
+            ("resourceType", string("GMObject")),
+        ],
+    };
+
+    object_txt(&fields, 0, format)
+}
+
+/// The `.yy` resource of a script.
+pub fn script_yy(script: &GameMakerScript, format: ResourceFormat) -> String {
+    let folder = folder_ref(SCRIPTS_FOLDER, 1, format);
+
+    let fields = match format {
+        ResourceFormat::Modern => vec![
+            ("$GMScript", string("v1")),
+            ("%Name", string(script.name)),
+            ("isCompatibility", "false".to_string()),
+            ("isDnD", "false".to_string()),
+            ("name", string(script.name)),
+            ("parent", folder),
+            ("resourceType", string("GMScript")),
+            ("resourceVersion", string("2.0")),
+        ],
+        ResourceFormat::Legacy => vec![
+            ("isDnD", "false".to_string()),
+            ("isCompatibility", "false".to_string()),
+            ("parent", folder),
+            ("resourceVersion", string("1.0")),
+            ("name", string(script.name)),
+            ("tags", "[]".to_string()),
+            ("resourceType", string("GMScript")),
+        ],
+    };
+
+    object_txt(&fields, 0, format)
+}
+
+/// The `.yyp` file of the project, listing every resource and the folders of
+/// the asset browser.
+pub fn project_yyp(project: &GameMakerProject, name: &str) -> String {
+    let format = project.ide_version.resource_format();
+
+    let paths = project
+        .objects
+        .iter()
+        .map(|object| ("objects", object.name))
+        .chain(
+            project
+                .scripts
+                .iter()
+                .map(|script| ("scripts", script.name)),
+        );
+    let resources: Vec<String> = paths
+        .enumerate()
+        .map(|(i, (dir, resource))| {
+            let id = inline_object(&[
+                ("name", string(resource)),
+                ("path", string(&resource_path(dir, resource))),
+            ]);
+            match format {
+                ResourceFormat::Modern => inline_object(&[("id", id)]),
+                ResourceFormat::Legacy => inline_object(&[("id", id), ("order", i.to_string())]),
+            }
+        })
+        .collect();
+
+    let folders: Vec<String> = [OBJECTS_FOLDER, SCRIPTS_FOLDER]
+        .into_iter()
+        .enumerate()
+        .map(|(i, folder)| {
+            let path = string(&format!("folders/{}.yy", folder));
+            match format {
+                ResourceFormat::Modern => inline_object(&[
+                    ("$GMFolder", string("")),
+                    ("%Name", string(folder)),
+                    ("folderPath", path),
+                    ("name", string(folder)),
+                    ("resourceType", string("GMFolder")),
+                    ("resourceVersion", string("2.0")),
+                ]),
+                ResourceFormat::Legacy => inline_object(&[
+                    ("folderPath", path),
+                    ("order", (i + 1).to_string()),
+                    ("resourceVersion", string("1.0")),
+                    ("name", string(folder)),
+                    ("tags", "[]".to_string()),
+                    ("resourceType", string("GMFolder")),
+                ]),
+            }
+        })
+        .collect();
+
+    let configs = object_txt(
+        &[("children", "[]".to_string()), ("name", string("Default"))],
+        1,
+        format,
+    );
+    let meta_data = object_txt(
+        &[("IDEVersion", string(&project.ide_version.to_string()))],
+        1,
+        format,
+    );
+
+    let fields = match format {
+        ResourceFormat::Modern => {
+            let audio_group = inline_object(&[
+                ("$GMAudioGroup", string("v1")),
+                ("%Name", string("audiogroup_default")),
+                ("exportDir", string("")),
+                ("name", string("audiogroup_default")),
+                ("resourceType", string("GMAudioGroup")),
+                ("resourceVersion", string("2.0")),
+                ("targets", "-1".to_string()),
+            ]);
+            let texture_group = inline_object(&[
+                ("$GMTextureGroup", string("")),
+                ("%Name", string("Default")),
+                ("autocrop", "true".to_string()),
+                ("border", "2".to_string()),
+                ("compressFormat", string("bz2")),
+                ("customOptions", string("")),
+                ("directory", string("")),
+                ("groupParent", "null".to_string()),
+                ("isScaled", "true".to_string()),
+                ("loadType", string("default")),
+                ("mipsToGenerate", "0".to_string()),
+                ("name", string("Default")),
+                ("resourceType", string("GMTextureGroup")),
+                ("resourceVersion", string("2.0")),
+                ("targets", "-1".to_string()),
+            ]);
+
+            vec![
+                ("$GMProject", string("")),
+                ("%Name", string(name)),
+                ("AudioGroups", array(&[audio_group], 1)),
+                ("configs", configs),
+                ("defaultScriptType", "1".to_string()),
+                ("Folders", array(&folders, 1)),
+                ("IncludedFiles", "[]".to_string()),
+                ("isEcma", "false".to_string()),
+                ("LibraryEmitters", "[]".to_string()),
+                ("MetaData", meta_data),
+                ("name", string(name)),
+                ("resources", array(&resources, 1)),
+                ("resourceType", string("GMProject")),
+                ("resourceVersion", string("2.0")),
+                ("RoomOrderNodes", "[]".to_string()),
+                ("templateType", string("game")),
+                ("TextureGroups", array(&[texture_group], 1)),
+            ]
+        }
+        ResourceFormat::Legacy => {
+            let audio_group = inline_object(&[
+                ("targets", "-1".to_string()),
+                ("resourceVersion", string("1.3")),
+                ("name", string("audiogroup_default")),
+                ("resourceType", string("GMAudioGroup")),
+            ]);
+            let texture_group = inline_object(&[
+                ("isScaled", "true".to_string()),
+                ("autocrop", "true".to_string()),
+                ("border", "2".to_string()),
+                ("mipsToGenerate", "0".to_string()),
+                ("groupParent", "null".to_string()),
+                ("targets", "-1".to_string()),
+                ("resourceVersion", string("1.0")),
+                ("name", string("Default")),
+                ("resourceType", string("GMTextureGroup")),
+            ]);
+
+            vec![
+                ("resources", array(&resources, 1)),
+                ("Options", "[]".to_string()),
+                ("isDnDProject", "false".to_string()),
+                ("isEcma", "false".to_string()),
+                ("tutorialPath", string("")),
+                ("configs", configs),
+                ("RoomOrderNodes", "[]".to_string()),
+                ("Folders", array(&folders, 1)),
+                ("AudioGroups", array(&[audio_group], 1)),
+                ("TextureGroups", array(&[texture_group], 1)),
+                ("IncludedFiles", "[]".to_string()),
+                ("MetaData", meta_data),
+                ("resourceVersion", string("1.6")),
+                ("name", string(name)),
+                ("tags", "[]".to_string()),
+                ("resourceType", string("GMProject")),
+            ]
+        }
+    };
+
+    object_txt(&fields, 0, format)
+}
+
+/// Whether `name` can name an asset: letters, digits and underscores, not
+/// starting with a digit.
+pub fn is_asset_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(ch) if ch.is_ascii_alphabetic() || ch == '_')
+        && chars.all(|ch| ch.is_ascii_alphanumeric() || ch == '_')
+}
+
+fn resource_path(dir: &str, name: &str) -> String {
+    format!("{}/{}/{}.yy", dir, name, name)
+}
+
+/// Reference to the resource `name` stored in `dir`, or `null`.
+fn resource_ref(dir: &str, name: Option<&str>, indent: usize, format: ResourceFormat) -> String {
+    match name {
+        Some(name) => object_txt(
+            &[
+                ("name", string(name)),
+                ("path", string(&resource_path(dir, name))),
+            ],
+            indent,
+            format,
+        ),
+        None => "null".to_string(),
+    }
+}
+
+/// Reference to the object of a collision event, on a single line.
+fn collision_ref(object: Option<&str>) -> String {
+    match object {
+        Some(object) => inline_object(&[
+            ("name", string(object)),
+            ("path", string(&resource_path("objects", object))),
+        ]),
+        None => "null".to_string(),
+    }
+}
+
+fn folder_ref(name: &str, indent: usize, format: ResourceFormat) -> String {
+    object_txt(
+        &[
+            ("name", string(name)),
+            ("path", string(&format!("folders/{}.yy", name))),
+        ],
+        indent,
+        format,
+    )
+}
+
+/// JSON object with one key per line, closed at the `indent` level.
+fn object_txt(fields: &[(&str, String)], indent: usize, format: ResourceFormat) -> String {
+    let separator = match format {
+        ResourceFormat::Modern => ":",
+        ResourceFormat::Legacy => ": ",
+    };
+
+    let mut txt = "{\n".to_string();
+    for (key, value) in fields {
+        txt.push_str(&"  ".repeat(indent + 1));
+        txt.push_str(&format!("{}{}{},\n", string(key), separator, value));
+    }
+    txt.push_str(&"  ".repeat(indent));
+    txt.push('}');
+
+    txt
+}
+
+/// JSON object on a single line, used for array items.
+fn inline_object(fields: &[(&str, String)]) -> String {
+    let fields: String = fields
+        .iter()
+        .map(|(key, value)| format!("{}:{},", string(key), value))
+        .collect();
+
+    format!("{{{}}}", fields)
+}
+
+/// JSON array with one item per line, `[]` when empty.
+fn array(items: &[String], indent: usize) -> String {
+    if items.is_empty() {
+        return "[]".to_string();
+    }
+
+    let mut txt = "[\n".to_string();
+    for item in items {
+        txt.push_str(&"  ".repeat(indent + 1));
+        txt.push_str(item);
+        txt.push_str(",\n");
+    }
+    txt.push_str(&"  ".repeat(indent));
+    txt.push(']');
+
+    txt
+}
+
+fn string(txt: &str) -> String {
+    let mut escaped = "\"".to_string();
+    for ch in txt.chars() {
+        match ch {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            ch if ch.is_control() => escaped.push_str(&format!("\\u{:04x}", ch as u32)),
+            ch => escaped.push(ch),
+        }
+    }
+    escaped.push('"');
+
+    escaped
+}
@@ -1,11 +1,13 @@
 pub mod ast;
+pub mod checker;
 pub mod organizer;
 pub mod text_data;
 pub mod tokenizer;
 pub mod parser;
 pub mod gamemaker;
 
-pub mod tests;
+#[cfg(test)]
+mod tests;
 
 pub trait Boxxable {
     fn to_box(self) -> Box<Self>
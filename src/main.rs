@@ -1,13 +1,16 @@
-use yoyogay::{gamemaker::GameMakerProject, organizer::{OrganizeError, YoyogayProject}, parser::{parse_tks, ParseError}, tokenizer::{tokenize, TokenizeError}};
+use yoyogay::{gamemaker::{resource::InvalidIdeVersion, GameMakerProject}, organizer::{Lints, OrganizeError, YoyogayProject}, parser::ParseError, tokenizer::TokenizeError};
 
 #[derive(Debug)]
+#[allow(clippy::enum_variant_names)]
 enum Error {
     #[allow(unused)]
     OrganizeError(OrganizeError),
     #[allow(unused)]
     TokenizeError(TokenizeError),
     #[allow(unused)]
-    ParseError(ParseError),
+    ParseErrors(Vec<ParseError>),
+    #[allow(unused)]
+    InvalidIdeVersion(InvalidIdeVersion),
 }
 
 impl From<TokenizeError> for Error {
@@ -22,15 +25,33 @@ impl From<OrganizeError> for Error {
     }
 }
 
-impl From<ParseError> for Error {
-    fn from(value: ParseError) -> Self {
-        Error::ParseError(value)
+impl From<Vec<ParseError>> for Error {
+    fn from(value: Vec<ParseError>) -> Self {
+        Error::ParseErrors(value)
+    }
+}
+
+impl From<InvalidIdeVersion> for Error {
+    fn from(value: InvalidIdeVersion) -> Self {
+        Error::InvalidIdeVersion(value)
     }
 }
 
 fn main() -> Result<(), Error> {
-    let yoyogay_project = YoyogayProject::create_from_directory("./test_project").map_err(|e| Error::OrganizeError(e))?;
-    let gamemaker_project = GameMakerProject::new_from_yoyogay_project(&yoyogay_project);
+    let mut lints = Lints::default();
+    if let Ok(names) = std::env::var("YOYOGAY_LINTS") {
+        lints.missing_super_create = names.split(',').any(|name| name.trim() == "missing_super");
+    }
+
+    let yoyogay_project = YoyogayProject::create_from_directory("./test_project")?;
+    let checked_project = yoyogay_project.check(lints)?;
+    for (path, warning) in &checked_project.warnings {
+        eprintln!("warning: {}: {:?}", path.display(), warning);
+    }
+    let mut gamemaker_project = GameMakerProject::new_from_yoyogay_project(&checked_project)?;
+    if let Ok(version) = std::env::var("YOYOGAY_IDE_VERSION") {
+        gamemaker_project.ide_version = version.parse()?;
+    }
     gamemaker_project.write_in_fs("./output_project").unwrap();
     Ok(())
 }
@@ -1,11 +1,47 @@
 use std::path::PathBuf;
 
+use crate::checker::resolver::ResolveError;
+
 pub mod object;
 pub mod info;
+pub mod script;
+pub mod source;
 
 #[derive(Debug)]
 pub struct YoyogayProject {
     pub objects: Vec<object::Object>,
+    pub scripts: Vec<script::Script>,
+    /// Names listed in `assets.txt`, for sprites, sounds and other assets made
+    /// in GameMaker that the code can refer to.
+    pub assets: Vec<String>,
+}
+
+/// A project with every event and script parsed once and checked. The trees
+/// borrow the tokens of the `YoyogayProject` it was made from.
+pub struct CheckedProject<'p> {
+    pub objects: Vec<object::ParsedObject<'p>>,
+    pub scripts: Vec<script::ParsedScript<'p>>,
+    /// Table of everything the project declares.
+    pub info: info::ProjectInfo<'p>,
+    /// Warnings, along with the file they were found in.
+    pub warnings: Vec<(PathBuf, Warning)>,
+}
+
+/// Something that compiles but is likely a mistake.
+#[derive(Debug, PartialEq)]
+pub enum Warning {
+    /// Found by one of the enabled lints.
+    Lint(object::ObjectWarning),
+    /// A name that doesn't stop the project from compiling, like a parameter
+    /// called `x` shadowing the instance variable.
+    Resolve(ResolveError),
+}
+
+/// Checks that aren't errors and are off unless asked for.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Lints {
+    /// Warn when a child object has a create event that never calls `super();`.
+    pub missing_super_create: bool,
 }
 
 #[derive(Debug)]
@@ -13,35 +49,119 @@ pub enum OrganizeError {
     FailedReadingFromFileSystem(std::io::Error),
     UnexpectedFile(PathBuf),
     ObjectParseError(object::ObjectParseError),
+    /// Every error found in the object files, along with the file it was found in.
+    ObjectParseErrors(Vec<(PathBuf, object::ObjectParseError)>),
+    /// Every error found in the script files, along with the file it was found in.
+    ScriptParseErrors(Vec<(PathBuf, script::ScriptParseError)>),
 }
 
 impl YoyogayProject {
+    /// Reads and tokenizes every object and script. `check` parses and checks them.
     pub fn create_from_directory(path: impl Into<PathBuf>) -> Result<YoyogayProject, OrganizeError> {
         let path = path.into();
         let mut objects: Option<Vec<object::Object>> = None;
+        let mut scripts: Option<Vec<script::Script>> = None;
+        let mut assets: Vec<String> = Vec::new();
 
         let dirs = path
             .read_dir()
-            .map_err(|e| OrganizeError::FailedReadingFromFileSystem(e))?;
+            .map_err(OrganizeError::FailedReadingFromFileSystem)?;
         for dir in dirs {
-            let dir = dir.map_err(|e| OrganizeError::FailedReadingFromFileSystem(e))?;
+            let dir = dir.map_err(OrganizeError::FailedReadingFromFileSystem)?;
             let path = dir.path();
+            let name = dir.file_name();
+
+            if name == "assets.txt" && path.is_file() {
+                let list = std::fs::read_to_string(&path).map_err(OrganizeError::FailedReadingFromFileSystem)?;
+                assets = read_asset_list(&list);
+                continue;
+            }
 
             if !path.is_dir() {
                 return Err(OrganizeError::UnexpectedFile(path));
             }
 
-            let name = dir.file_name();
-
             match name.as_os_str().to_str().unwrap_or("") {
                 "objects" => {
                     objects = Some(object::organize_objects(path)?);
                 }
-                _ => panic!("Unexpected directory: {:?}", path),
+                "scripts" => {
+                    scripts = Some(script::organize_scripts(path)?);
+                }
+                _ => return Err(OrganizeError::UnexpectedFile(path)),
+            }
+        }
+
+        Ok(YoyogayProject {
+            objects: objects.unwrap_or_default(),
+            scripts: scripts.unwrap_or_default(),
+            assets,
+        })
+    }
+
+    /// Parses every object and script, checks their names and types and runs
+    /// the enabled lints. Code can refer to anything in the project, so this
+    /// waits until every file is read.
+    pub fn check(&self, lints: Lints) -> Result<CheckedProject<'_>, OrganizeError> {
+        let objects: Vec<object::ParsedObject> = self.objects.iter().map(object::ParsedObject::parse).collect();
+        let scripts: Vec<script::ParsedScript> = self.scripts.iter().map(script::ParsedScript::parse).collect();
+        let info = info::ProjectInfo::new(&objects, &scripts, &self.assets);
+
+        let mut warnings: Vec<(PathBuf, Warning)> = Vec::new();
+
+        let mut script_errors: Vec<(PathBuf, script::ScriptParseError)> = Vec::new();
+        for parsed in &scripts {
+            let path = &parsed.script.path;
+            for err in script::check_script(parsed, &info) {
+                match err {
+                    script::ScriptParseError::ResolveError(err) if err.is_warning() => {
+                        warnings.push((path.clone(), Warning::Resolve(err)))
+                    }
+                    err => script_errors.push((path.clone(), err)),
+                }
+            }
+        }
+        // Objects call into scripts, so broken scripts are reported first.
+        if !script_errors.is_empty() {
+            return Err(OrganizeError::ScriptParseErrors(script_errors));
+        }
+
+        let mut object_errors: Vec<(PathBuf, object::ObjectParseError)> = Vec::new();
+        for parsed in &objects {
+            let path = &parsed.object.path;
+            for err in object::check_object(parsed, &info) {
+                match err {
+                    object::ObjectParseError::ResolveError(err) if err.is_warning() => {
+                        warnings.push((path.clone(), Warning::Resolve(err)))
+                    }
+                    err => object_errors.push((path.clone(), err)),
+                }
+            }
+        }
+        if !object_errors.is_empty() {
+            return Err(OrganizeError::ObjectParseErrors(object_errors));
+        }
+
+        for parsed in &objects {
+            for warning in object::lint_object(parsed, &lints) {
+                warnings.push((parsed.object.path.clone(), Warning::Lint(warning)));
             }
         }
-        let objects = objects.unwrap_or(Vec::new());
 
-        Ok(YoyogayProject { objects })
+        Ok(CheckedProject {
+            objects,
+            scripts,
+            info,
+            warnings,
+        })
     }
 }
+
+/// One name per line. Blank lines and lines starting with `//` are skipped.
+fn read_asset_list(list: &str) -> Vec<String> {
+    list.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with("//"))
+        .map(str::to_string)
+        .collect()
+}
@@ -1,17 +1,410 @@
-use std::collections::HashMap;
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+};
 
-use crate::parser::types::YoyogayType;
+use crate::{
+    ast::{DeclarationType, Node, NodeData},
+    checker::type_check::returns_value,
+    gamemaker::event::GameMakerEvent,
+    parser::types::YoyogayType,
+    text_data::{TextPos, TextRange},
+};
 
+use super::{
+    object::{ParsedEvent, ParsedObject},
+    script::ParsedScript,
+};
+
+/// Everything declared in the project that code can refer to from another file.
+#[derive(Default)]
 pub struct ProjectInfo<'a> {
+    /// Functions declared at the top of the scripts.
     pub functions: HashMap<String, YoyogayFunction<'a>>,
+    pub objects: HashMap<String, YoyogayObject<'a>>,
+    /// Constants declared with `const` at the top of the scripts.
+    pub constants: HashMap<String, YoyogayField<'a>>,
+    /// Variables assigned through `global`, and where they are first assigned.
+    pub globals: HashMap<String, SymbolLocation>,
+    /// Assets made in GameMaker rather than in the project, like sprites and
+    /// sounds.
+    pub assets: HashSet<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SymbolLocation {
+    pub path: PathBuf,
+    pub text_range: TextRange,
+}
+
+pub struct YoyogayObject<'a> {
+    /// The id in its `#id` header.
+    pub location: SymbolLocation,
+    /// Object given by its `#parent` directive.
+    pub parent: Option<String>,
+    /// Instance variables declared with `var` in the create event.
+    pub fields: HashMap<String, YoyogayField<'a>>,
+    /// Functions declared at the top of the create event, which every other
+    /// event can call.
+    pub functions: HashMap<String, YoyogayFunction<'a>>,
+}
+
+pub struct YoyogayField<'a> {
+    pub r#type: Option<YoyogayType<'a>>,
+    pub location: SymbolLocation,
 }
 
 pub struct YoyogayFunction<'a> {
     pub params: Vec<YoyogayParameter<'a>>,
-    pub return_type: YoyogayType<'a>,
+    /// `None` for functions without a `-> Type`.
+    pub return_type: Option<YoyogayType<'a>>,
+    /// Whether it has a `return value;`. Functions without a `-> Type` return
+    /// nothing unless they do.
+    pub returns_value: bool,
+    pub location: SymbolLocation,
 }
 
 pub struct YoyogayParameter<'a> {
     pub name: &'a String,
-    pub r#type: YoyogayType<'a>,
+    pub r#type: Option<YoyogayType<'a>>,
+}
+
+/// Anything a name can refer to across the project.
+pub enum Symbol<'i, 'a> {
+    Function(&'i YoyogayFunction<'a>),
+    Object(&'i YoyogayObject<'a>),
+    Constant(&'i YoyogayField<'a>),
+    Global(&'i SymbolLocation),
+}
+
+/// A field or function of an object.
+pub enum Member<'i, 'a> {
+    Field(&'i YoyogayField<'a>),
+    Function(&'i YoyogayFunction<'a>),
+}
+
+impl<'a> ProjectInfo<'a> {
+    /// Collects the declarations of every object. Events that fail to parse
+    /// still contribute whatever could be recovered from them.
+    pub fn from_objects(objects: &[ParsedObject<'a>]) -> ProjectInfo<'a> {
+        ProjectInfo::new(objects, &[], &[])
+    }
+
+    /// Collects the declarations of every object and script, along with the
+    /// names of the assets the project lists.
+    pub fn new(
+        objects: &[ParsedObject<'a>],
+        scripts: &[ParsedScript<'a>],
+        assets: &[String],
+    ) -> ProjectInfo<'a> {
+        let mut info = ProjectInfo {
+            assets: assets.iter().cloned().collect(),
+            ..Default::default()
+        };
+
+        for ParsedScript { script, source } in scripts {
+            let program = &source.program;
+
+            if let NodeData::Program(statements) = &*program.data {
+                for stmt in statements {
+                    match &*stmt.data {
+                        NodeData::FunctionDeclaration(id, ..) => {
+                            if let (NodeData::Identifier(name), Some(function)) = (
+                                &*id.data,
+                                YoyogayFunction::from_declaration(stmt, &script.path),
+                            ) {
+                                info.functions.entry(name.to_string()).or_insert(function);
+                            }
+                        }
+                        NodeData::VariableDeclaration(DeclarationType::Const, id, r#type, _, _) => {
+                            if let NodeData::Identifier(name) = &*id.data {
+                                let constant = YoyogayField {
+                                    r#type: r#type.as_ref().and_then(type_of_node),
+                                    location: SymbolLocation::new(&script.path, id),
+                                };
+                                info.constants.entry(name.to_string()).or_insert(constant);
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            collect_globals(program, &script.path, &mut info.globals);
+        }
+
+        for ParsedObject { object, events } in objects {
+            let mut yoyogay_object = YoyogayObject {
+                location: SymbolLocation {
+                    path: object.path.clone(),
+                    // `#id ` is always the start of the first line.
+                    text_range: TextRange {
+                        start: TextPos { line: 1, pos: 5 },
+                        end: TextPos {
+                            line: 1,
+                            pos: 4 + object.id.chars().count(),
+                        },
+                    },
+                },
+                parent: object.properties.parent.clone(),
+                fields: HashMap::new(),
+                functions: HashMap::new(),
+            };
+
+            for ParsedEvent { event, source } in events {
+                let program = &source.program;
+
+                if event.event == GameMakerEvent::CREATE {
+                    yoyogay_object.collect_members(program, &object.path);
+                }
+                collect_globals(program, &object.path, &mut info.globals);
+            }
+
+            info.objects.insert(object.id.clone(), yoyogay_object);
+        }
+
+        info
+    }
+
+    pub fn lookup(&self, name: &str) -> Option<Symbol<'_, 'a>> {
+        if let Some(function) = self.functions.get(name) {
+            Some(Symbol::Function(function))
+        } else if let Some(object) = self.objects.get(name) {
+            Some(Symbol::Object(object))
+        } else if let Some(constant) = self.constants.get(name) {
+            Some(Symbol::Constant(constant))
+        } else {
+            self.globals.get(name).map(Symbol::Global)
+        }
+    }
+
+    pub fn object(&self, id: &str) -> Option<&YoyogayObject<'a>> {
+        self.objects.get(id)
+    }
+
+    /// The object and the objects it inherits from, closest first. Stops
+    /// before an object that was already visited, if parents form a cycle.
+    pub fn ancestry(&self, id: &str) -> Vec<(&String, &YoyogayObject<'a>)> {
+        let mut ancestry: Vec<(&String, &YoyogayObject<'a>)> = Vec::new();
+        let mut next = self.objects.get_key_value(id);
+
+        while let Some((id, object)) = next {
+            if ancestry.iter().any(|(found, _)| *found == id) {
+                break;
+            }
+            ancestry.push((id, object));
+            next = object
+                .parent
+                .as_ref()
+                .and_then(|parent| self.objects.get_key_value(parent));
+        }
+
+        ancestry
+    }
+
+    /// The objects the parents of `id` go through before leading back to it,
+    /// starting with `id` itself. `None` if they don't lead back to it.
+    pub fn parent_cycle(&self, id: &str) -> Option<Vec<&String>> {
+        let ancestry = self.ancestry(id);
+        let (_, last) = ancestry.last()?;
+        if last.parent.as_deref() != Some(id) {
+            return None;
+        }
+
+        Some(ancestry.into_iter().map(|(id, _)| id).collect())
+    }
+
+    /// A member of an object or of the objects it inherits from.
+    pub fn member(&self, id: &str, name: &str) -> Option<Member<'_, 'a>> {
+        self.ancestry(id)
+            .into_iter()
+            .find_map(|(_, object)| object.member(name))
+    }
+
+    /// Names of the fields and functions of an object, including inherited
+    /// ones, sorted.
+    pub fn members_of(&self, id: &str) -> Vec<&String> {
+        let mut members: Vec<&String> = self
+            .ancestry(id)
+            .into_iter()
+            .flat_map(|(_, object)| object.members())
+            .collect();
+        members.sort();
+        members.dedup();
+
+        members
+    }
+}
+
+impl<'a> YoyogayObject<'a> {
+    pub fn member(&self, name: &str) -> Option<Member<'_, 'a>> {
+        match self.fields.get(name) {
+            Some(field) => Some(Member::Field(field)),
+            None => self.functions.get(name).map(Member::Function),
+        }
+    }
+
+    /// Names of its fields and functions, sorted.
+    pub fn members(&self) -> Vec<&String> {
+        let mut members: Vec<&String> = self.fields.keys().chain(self.functions.keys()).collect();
+        members.sort();
+
+        members
+    }
+
+    fn collect_members(&mut self, node: &Node<'a>, path: &Path) {
+        match &*node.data {
+            NodeData::Program(statements) => {
+                for stmt in statements {
+                    self.collect_members(stmt, path);
+                }
+            }
+            NodeData::VariableDeclaration(DeclarationType::Var, id, r#type, _, _) => {
+                if let NodeData::Identifier(name) = &*id.data {
+                    let field = YoyogayField {
+                        r#type: r#type.as_ref().and_then(type_of_node),
+                        location: SymbolLocation::new(path, id),
+                    };
+                    self.fields.entry(name.to_string()).or_insert(field);
+                }
+            }
+            NodeData::FunctionDeclaration(id, ..) => {
+                if let (NodeData::Identifier(name), Some(function)) =
+                    (&*id.data, YoyogayFunction::from_declaration(node, path))
+                {
+                    self.functions.entry(name.to_string()).or_insert(function);
+                }
+            }
+            // Fields declared in nested blocks still end up on the instance.
+            NodeData::If(_, body, else_node) => {
+                self.collect_members_in_block(body, path);
+                if let Some(else_node) = else_node {
+                    self.collect_members_in_block(else_node, path);
+                }
+            }
+            NodeData::Else(body)
+            | NodeData::While(_, body)
+            | NodeData::Loop(body)
+            | NodeData::Repeat(_, body)
+            | NodeData::DoUntil(body, _)
+            | NodeData::For(_, _, body) => self.collect_members_in_block(body, path),
+            _ => {}
+        }
+    }
+
+    /// Like `collect_members`, but functions in nested blocks aren't members.
+    fn collect_members_in_block(&mut self, node: &Node<'a>, path: &Path) {
+        match &*node.data {
+            NodeData::Program(statements) => {
+                for stmt in statements {
+                    if !matches!(&*stmt.data, NodeData::FunctionDeclaration(..)) {
+                        self.collect_members(stmt, path);
+                    }
+                }
+            }
+            _ => self.collect_members(node, path),
+        }
+    }
+}
+
+impl SymbolLocation {
+    fn new(path: &Path, node: &Node) -> SymbolLocation {
+        SymbolLocation {
+            path: path.to_path_buf(),
+            text_range: node.text_range.clone().into(),
+        }
+    }
+}
+
+impl<'a> YoyogayFunction<'a> {
+    /// Signature of a `NodeData::FunctionDeclaration` found in the file at
+    /// `path`, `None` for any other node.
+    pub fn from_declaration(node: &Node<'a>, path: &Path) -> Option<YoyogayFunction<'a>> {
+        let NodeData::FunctionDeclaration(name, params, return_type, body, _) = &*node.data else {
+            return None;
+        };
+
+        let params = params
+            .iter()
+            .filter_map(|param| match &*param.data {
+                NodeData::FunctionParemeter(id, r#type) => match &*id.data {
+                    NodeData::Identifier(name) => Some(YoyogayParameter {
+                        name,
+                        r#type: r#type.as_ref().and_then(type_of_node),
+                    }),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect();
+
+        Some(YoyogayFunction {
+            params,
+            return_type: return_type.as_ref().and_then(type_of_node),
+            returns_value: returns_value(body),
+            location: SymbolLocation::new(path, name),
+        })
+    }
+}
+
+fn type_of_node<'a>(node: &Node<'a>) -> Option<YoyogayType<'a>> {
+    match &*node.data {
+        NodeData::Type(r#type) => Some(r#type.clone()),
+        _ => None,
+    }
+}
+
+/// Records every `global.name = ...` in `node`.
+fn collect_globals(node: &Node, path: &Path, globals: &mut HashMap<String, SymbolLocation>) {
+    match &*node.data {
+        NodeData::Program(statements) => {
+            for stmt in statements {
+                collect_globals(stmt, path, globals);
+            }
+        }
+        NodeData::VariableModification(target, _, _) => {
+            let targets = match &*target.data {
+                NodeData::Tuple(targets) => targets.iter().collect(),
+                _ => vec![target],
+            };
+
+            for target in targets {
+                if let Some(name) = global_name(target) {
+                    globals
+                        .entry(name.to_string())
+                        .or_insert_with(|| SymbolLocation::new(path, target));
+                }
+            }
+        }
+        NodeData::FunctionDeclaration(_, _, _, body, _)
+        | NodeData::Else(body)
+        | NodeData::While(_, body)
+        | NodeData::Loop(body)
+        | NodeData::Repeat(_, body)
+        | NodeData::DoUntil(body, _)
+        | NodeData::For(_, _, body) => collect_globals(body, path, globals),
+        NodeData::If(_, body, else_node) => {
+            collect_globals(body, path, globals);
+            if let Some(else_node) = else_node {
+                collect_globals(else_node, path, globals);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The name in `global.name` or `global::name`.
+pub fn global_name<'n>(node: &'n Node) -> Option<&'n String> {
+    match &*node.data {
+        NodeData::StructAccess(value, property) | NodeData::ModAccess(value, property) => {
+            match (&*value.data, &*property.data) {
+                (NodeData::Identifier(value), NodeData::Identifier(property))
+                    if value.as_str() == "global" =>
+                {
+                    Some(property)
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
 }
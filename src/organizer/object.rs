@@ -1,191 +1,393 @@
-use std::{collections::HashMap, ffi::OsStr, fs::File, io::{BufRead, BufReader}, path::PathBuf};
+use std::{collections::HashMap, ffi::OsStr, fs::File, io::{BufRead, BufReader}, path::{Path, PathBuf}};
 
-use crate::{ast::Node, parser::{parse_tks, ParseError}, tokenizer::{tokenize, Token, TokenizeError}};
+use crate::{ast::{Node, NodeData}, checker::{resolver::{resolve_names, ResolveError}, type_check::{check_types, TypeError}}, gamemaker::{event::GameMakerEvent, resource::is_asset_name, ObjectProperties}, parser::ParseError, tokenizer::{tokenize, Token, TokenizeError}};
 
-use super::OrganizeError;
+use super::{info::ProjectInfo, source::Source, Lints, OrganizeError};
 
 #[derive(Debug)]
-pub struct Object<'a> {
+pub struct Object {
     pub id: String,
-    pub create: Option<(Node<'a>, Vec<Token>)>,
-    pub step: Option<(Node<'a>, Vec<Token>)>,
-    pub draw: Option<(Node<'a>, Vec<Token>)>,
-    pub draw_gui: Option<(Node<'a>, Vec<Token>)>,
-    pub clean_up: Option<(Node<'a>, Vec<Token>)>,
+    /// File the object was read from.
+    pub path: PathBuf,
+    /// Settings from the header directives, like `#sprite spr_player`.
+    pub properties: ObjectProperties,
+    /// Line of every header directive, by name.
+    pub directive_lines: HashMap<String, usize>,
+    /// Every event, in the order they appear in the file.
+    pub events: Vec<ObjectEvent>,
+}
+
+#[derive(Debug)]
+pub struct ObjectEvent {
+    pub event: GameMakerEvent,
+    /// Line of its `#event` header.
+    pub line: usize,
+    pub tokens: Vec<Token>,
+}
+
+impl Object {
+    pub fn event(&self, event: &GameMakerEvent) -> Option<&ObjectEvent> {
+        self.events.iter().find(|found| found.event == *event)
+    }
+}
+
+/// An object with every event parsed, in the order they appear in the file.
+#[derive(Debug)]
+pub struct ParsedObject<'p> {
+    pub object: &'p Object,
+    pub events: Vec<ParsedEvent<'p>>,
+}
+
+#[derive(Debug)]
+pub struct ParsedEvent<'p> {
+    pub event: &'p ObjectEvent,
+    pub source: Source<'p>,
+}
+
+impl<'p> ParsedObject<'p> {
+    pub fn parse(object: &'p Object) -> ParsedObject<'p> {
+        let events = object
+            .events
+            .iter()
+            .map(|event| ParsedEvent { event, source: Source::parse(&event.tokens) })
+            .collect();
+
+        ParsedObject { object, events }
+    }
+
+    pub fn event(&self, event: &GameMakerEvent) -> Option<&ParsedEvent<'p>> {
+        self.events.iter().find(|found| found.event.event == *event)
+    }
 }
 
 #[derive(Debug)]
 pub enum ObjectParseError {
     NoIdentification(String),
     UnknownEvent(String, usize),
+    /// An event written twice in the same file, and the line of the second one.
+    DuplicateEvent(String, usize),
+    /// A collision event with an object that doesn't exist, and its line.
+    UnknownCollisionObject(String, usize),
+    UnknownDirective(String, usize),
+    /// A directive given twice, and the line of the second one.
+    DuplicateDirective(String, usize),
+    /// The directive, its value and its line.
+    InvalidDirective(String, String, usize),
+    /// A `#parent` that isn't an object of the project, and its line.
+    UnknownParent(String, usize),
+    /// Objects whose parents lead back to the first one, and the line of its
+    /// `#parent`.
+    ParentCycle(Vec<String>, usize),
+    /// A `super();` in an object without a parent, and its line.
+    SuperWithoutParent(usize),
     TokenizeError(TokenizeError),
     ParseError(ParseError),
+    ResolveError(ResolveError),
+    TypeError(Box<TypeError>),
+}
+
+/// Something that compiles but is likely a mistake, only reported when its
+/// lint is enabled.
+#[derive(Debug, PartialEq)]
+pub enum ObjectWarning {
+    /// A create event of an object with a parent that never calls `super();`,
+    /// and the line of its `#event` header.
+    MissingSuperCall(usize),
 }
 
-impl Into<ObjectParseError> for TokenizeError {
-    fn into(self) -> ObjectParseError {
-        ObjectParseError::TokenizeError(self)
+impl From<TokenizeError> for ObjectParseError {
+    fn from(value: TokenizeError) -> Self {
+        ObjectParseError::TokenizeError(value)
     }
 }
 
 impl<T:Into<ObjectParseError>> From<T> for OrganizeError {
     fn from(value: T) -> OrganizeError {
-        value.into().into()
+        OrganizeError::ObjectParseError(value.into())
     }
 }
 
-impl Into<ObjectParseError> for ParseError {
-    fn into(self) -> ObjectParseError {
-        ObjectParseError::ParseError(self)
+impl From<ParseError> for ObjectParseError {
+    fn from(value: ParseError) -> Self {
+        ObjectParseError::ParseError(value)
     }
 }
 
-pub fn organize_objects<'a>(path: PathBuf) -> Result<Vec<Object<'a>>, OrganizeError> {
+impl From<ResolveError> for ObjectParseError {
+    fn from(value: ResolveError) -> Self {
+        ObjectParseError::ResolveError(value)
+    }
+}
+
+impl From<TypeError> for ObjectParseError {
+    fn from(value: TypeError) -> Self {
+        ObjectParseError::TypeError(Box::new(value))
+    }
+}
+
+pub fn organize_objects(path: PathBuf) -> Result<Vec<Object>, OrganizeError> {
     assert!(path.exists());
     assert!(path.is_dir());
     assert_eq!(path.file_name(), Some(OsStr::new("objects")));
 
-    let dirs = path.read_dir().expect("Couldnt read objects directory");
+    let dirs = path.read_dir().map_err(OrganizeError::FailedReadingFromFileSystem)?;
     let mut objects: Vec<Object> = Vec::new();
+    let mut errors: Vec<(PathBuf, ObjectParseError)> = Vec::new();
 
     for entry in dirs {
-        let entry = entry.expect("Couldnt read dir in read directory");
+        let entry = entry.map_err(OrganizeError::FailedReadingFromFileSystem)?;
         let path = entry.path();
         if !path.is_file() {
             return Err(OrganizeError::UnexpectedFile(path));
         }
-        let file = File::open(path).map_err(|e| OrganizeError::FailedReadingFromFileSystem(e))?;
 
-        let reader = BufReader::new(file);
-        let mut id: Option<String> = None;
-        let mut events: HashMap<String, (String, usize)> = HashMap::new();
+        let mut object_errors: Vec<ObjectParseError> = Vec::new();
+        match organize_object(&path, &mut object_errors) {
+            Ok(object) => objects.push(object),
+            Err(OrganizeError::ObjectParseError(err)) => object_errors.push(err),
+            Err(err) => return Err(err),
+        }
 
-        let mut current_event: Option<String> = None;
-        let mut line_buf: String = "".to_string();
+        errors.extend(object_errors.into_iter().map(|err| (path.clone(), err)));
+    }
+
+    if !errors.is_empty() {
+        return Err(OrganizeError::ObjectParseErrors(errors));
+    }
+
+    Ok(objects)
+}
 
-        let mut lines = reader.lines().peekable();
-        let mut current_line_idx: usize = 0;
-        while let Some(line) = lines.next() {
-            let line = line.map_err(|e| OrganizeError::FailedReadingFromFileSystem(e))?;
+/// Reads one object file. Errors in its events are pushed to `errors` so every
+/// event gets checked, anything that stops the whole file is returned.
+fn organize_object(path: &Path, errors: &mut Vec<ObjectParseError>) -> Result<Object, OrganizeError> {
+    let file = File::open(path).map_err(OrganizeError::FailedReadingFromFileSystem)?;
 
-            if current_line_idx == 0 {
-                if !line.starts_with("#id ") {
-                    return Err(OrganizeError::ObjectParseError(ObjectParseError::NoIdentification(line)));
-                }
+    let reader = BufReader::new(file);
+    let mut id: Option<String> = None;
+    let mut properties = ObjectProperties::default();
+    let mut directive_lines: HashMap<String, usize> = HashMap::new();
+    // Event name, its code and the number of lines in the file before it.
+    let mut events: Vec<(String, String, usize)> = Vec::new();
 
-                id = Some(line[4..].to_string());
+    // Event name and the number of lines in the file before its code.
+    let mut current_event: Option<(String, usize)> = None;
+    let mut line_buf: String = "".to_string();
 
-                current_line_idx += 1;
-                continue;
+    let mut lines = reader.lines().peekable();
+    let mut current_line_idx: usize = 0;
+    while let Some(line) = lines.next() {
+        let line = line.map_err(OrganizeError::FailedReadingFromFileSystem)?;
+
+        if current_line_idx == 0 {
+            if !line.starts_with("#id ") {
+                return Err(OrganizeError::ObjectParseError(ObjectParseError::NoIdentification(line)));
             }
 
-            if line.starts_with("#event ") {
-                if current_event.is_some() {
-                    events.insert(current_event.clone().unwrap(), (line_buf.clone(), current_line_idx+1));
-                }
+            id = Some(line[4..].to_string());
 
-                let event_name = &line[7..];
-                current_event = Some(event_name.to_string());
-                line_buf = "".to_string();
+            current_line_idx += 1;
+            continue;
+        }
 
-                current_line_idx += 1;
-                continue;
+        if let Some(event_name) = line.strip_prefix("#event ") {
+            if let Some((event, offset)) = current_event.take() {
+                events.push((event, line_buf.clone(), offset));
             }
 
-            let mut needs_to_push_new_line = false;
-            if lines.peek().is_none() {
-                if current_event.is_some() {
-                    events.insert(current_event.clone().unwrap(), (line_buf.clone(), current_line_idx+1));
-                }
+            current_event = Some((event_name.to_string(), current_line_idx + 1));
+            line_buf = "".to_string();
+
+            current_line_idx += 1;
+            continue;
+        }
+
+        // Directives go between `#id` and the first event.
+        if let (None, Some(directive)) = (&current_event, line.strip_prefix('#')) {
+            let line_number = current_line_idx + 1;
+            let (name, value) = directive.split_once(char::is_whitespace).unwrap_or((directive, ""));
+
+            if name == "id" || directive_lines.contains_key(name) {
+                errors.push(ObjectParseError::DuplicateDirective(name.to_string(), line_number));
+            } else if let Err(err) = apply_directive(&mut properties, name, value.trim(), line_number) {
+                errors.push(err);
             } else {
-                needs_to_push_new_line = true;
+                directive_lines.insert(name.to_string(), line_number);
             }
 
-            line_buf.push_str(&line);
-            if needs_to_push_new_line {
-                line_buf.push('\n');
-            }
             current_line_idx += 1;
+            continue;
         }
 
-        let id = id.expect("No Object ID");
-
-        let mut create: Option<String> = None;
-        let mut step: Option<String> = None;
-        let mut draw: Option<String> = None;
-        let mut draw_gui: Option<String> = None;
-        let mut clean_up: Option<String> = None;
-        for event in events.keys() {
-            let (src, line) = events.get(event).expect("?????????????????????");
-            match event.as_str() {
-                "create" => {
-                    create = Some(src.to_string());
-                }
-                "step" => {
-                    step = Some(src.to_string());
-                }
-                "draw" => {
-                    draw = Some(src.to_string());
-                }
-                "draw_gui" => {
-                    draw_gui = Some(src.to_string());
-                }
-                "clean_up" => {
-                    clean_up = Some(src.to_string());
-                }
-                _ => {
-                    return Err(ObjectParseError::UnknownEvent(src.to_string(), *line).into());
-                }
+        line_buf.push_str(&line);
+        if lines.peek().is_none() {
+            if let Some((event, offset)) = current_event.take() {
+                events.push((event, line_buf.clone(), offset));
             }
+        } else {
+            line_buf.push('\n');
         }
+        current_line_idx += 1;
+    }
 
-        macro_rules! parse_event {
-            ($event:ident) => {{
-                let event_tokens: Option<Result<Vec<Token>, TokenizeError>> = if let Some(src) = &$event {
-                    Some(tokenize(src))
-                } else {
-                    None
-                };
-
-                let event_tokens: Option<Vec<Token>> = if let Some(res) = event_tokens {
-                    Some(res?)
-                } else {
-                    None
-                };
-
-                let event: Option<Node> = if let Some(tks) = event_tokens {
-                    Some(parse_tks(&tks)?)
-                } else {
-                    None
-                };
-
-                (event, event_tokens)
-            }}
+    let id = id.ok_or(ObjectParseError::NoIdentification(String::new()))?;
+
+    let mut object_events: Vec<ObjectEvent> = Vec::new();
+    for (name, src, offset) in events {
+        let Some(event) = GameMakerEvent::from_name(&name) else {
+            errors.push(ObjectParseError::UnknownEvent(name, offset));
+            continue;
+        };
+        if object_events.iter().any(|found| found.event == event) {
+            errors.push(ObjectParseError::DuplicateEvent(name, offset));
+            continue;
         }
 
-        macro_rules! change_option {
-            ($event:ident) => {
-                
-            };
+        match tokenize(&src) {
+            Ok(mut tks) => {
+                shift_lines(&mut tks, offset);
+                object_events.push(ObjectEvent { event, line: offset, tokens: tks });
+            }
+            Err(mut err) => {
+                err.text_range.start.line += offset;
+                err.text_range.end.line += offset;
+                errors.push(err.into());
+            }
         }
+    }
 
-        let create: Option<(Node, Vec<Token>)> = parse_event!(create);
-        let step: Option<(Node, Vec<Token>)> = parse_event!(step);
-        let draw: Option<(Node, Vec<Token>)> = parse_event!(draw);
-        let draw_gui: Option<(Node, Vec<Token>)> = parse_event!(draw_gui);
-        let clean_up: Option<(Node, Vec<Token>)> = parse_event!(clean_up);
-        
-        let object = Object {
-            id,
-            create,
-            step,
-            draw,
-            draw_gui,
-            clean_up,
-        };
+    Ok(Object {
+        id,
+        path: path.to_path_buf(),
+        properties,
+        directive_lines,
+        events: object_events,
+    })
+}
+
+/// Sets the property named by a header directive like `#sprite spr_player`.
+fn apply_directive(properties: &mut ObjectProperties, name: &str, value: &str, line: usize) -> Result<(), ObjectParseError> {
+    let invalid = || ObjectParseError::InvalidDirective(name.to_string(), value.to_string(), line);
+    let asset = || is_asset_name(value).then(|| value.to_string()).ok_or_else(invalid);
+    let flag = || value.parse::<bool>().map_err(|_| invalid());
 
-        objects.push(object);
+    match name {
+        "parent" => properties.parent = Some(asset()?),
+        "sprite" => properties.sprite = Some(asset()?),
+        "mask" => properties.mask = Some(asset()?),
+        "visible" => properties.visible = flag()?,
+        "persistent" => properties.persistent = flag()?,
+        "solid" => properties.solid = flag()?,
+        "depth" => properties.depth = Some(value.parse().map_err(|_| invalid())?),
+        _ => return Err(ObjectParseError::UnknownDirective(name.to_string(), line)),
     }
 
-    Ok(objects)
+    Ok(())
+}
+
+/// Checks the names and types of every event of an object.
+pub fn check_object(parsed: &ParsedObject, info: &ProjectInfo) -> Vec<ObjectParseError> {
+    let object = parsed.object;
+    let mut errors: Vec<ObjectParseError> = Vec::new();
+
+    // Every other event can use the fields and functions declared in create.
+    let members: Vec<String> = info.members_of(&object.id).into_iter().cloned().collect();
+    let cycle = info.parent_cycle(&object.id);
+    // Create itself can use the ones its parents declare, which `super();` runs.
+    // Parents in a cycle lead back to the object's own, so only the cycle is reported.
+    let inherited: Vec<String> = match (&object.properties.parent, &cycle) {
+        (Some(parent), None) => info.members_of(parent).into_iter().cloned().collect(),
+        _ => Vec::new(),
+    };
+
+    if let Some(parent) = &object.properties.parent {
+        if info.object(parent).is_none() {
+            let line = object.directive_lines.get("parent").copied().unwrap_or(0);
+            errors.push(ObjectParseError::UnknownParent(parent.to_string(), line));
+        } else if let Some(cycle) = cycle {
+            let line = object.directive_lines.get("parent").copied().unwrap_or(0);
+            errors.push(ObjectParseError::ParentCycle(cycle.into_iter().cloned().collect(), line));
+        }
+    }
+
+    for ParsedEvent { event: ObjectEvent { event, line, .. }, source } in &parsed.events {
+        // Inside a collision event, `other` is the instance collided with.
+        let other = event.collision_object.as_deref();
+        if let Some(target) = other {
+            if info.object(target).is_none() {
+                errors.push(ObjectParseError::UnknownCollisionObject(target.to_string(), *line));
+            }
+        }
+
+        let Source { program, parse_errors } = source;
+
+        if object.properties.parent.is_none() {
+            let mut calls: Vec<&Node> = Vec::new();
+            super_calls(program, &mut calls);
+            errors.extend(calls.iter().map(|call| ObjectParseError::SuperWithoutParent(call.text_range.start.line)));
+        }
+
+        // Names and types are only worth checking in events that parsed cleanly.
+        if parse_errors.is_empty() {
+            let fields = if *event == GameMakerEvent::CREATE { inherited.as_slice() } else { members.as_slice() };
+            let (_, resolve_errors) = resolve_names(program, info, fields, other);
+            errors.extend(resolve_errors.into_iter().map(ObjectParseError::from));
+            errors.extend(check_types(program, info, other).into_iter().map(ObjectParseError::from));
+        }
+        errors.extend(parse_errors.iter().cloned().map(ObjectParseError::from));
+    }
+
+    errors
+}
+
+/// Runs the enabled lints on an object.
+pub fn lint_object(parsed: &ParsedObject, lints: &Lints) -> Vec<ObjectWarning> {
+    let mut warnings: Vec<ObjectWarning> = Vec::new();
+
+    if lints.missing_super_create && parsed.object.properties.parent.is_some() {
+        if let Some(create) = parsed.event(&GameMakerEvent::CREATE) {
+            let mut calls: Vec<&Node> = Vec::new();
+            super_calls(&create.source.program, &mut calls);
+            if calls.is_empty() {
+                warnings.push(ObjectWarning::MissingSuperCall(create.event.line));
+            }
+        }
+    }
+
+    warnings
+}
+
+/// Collects every `super();` in `node`, including the ones in nested blocks
+/// and functions.
+pub fn super_calls<'n, 'a>(node: &'n Node<'a>, calls: &mut Vec<&'n Node<'a>>) {
+    match &*node.data {
+        NodeData::Super => calls.push(node),
+        NodeData::Program(statements) => {
+            for stmt in statements {
+                super_calls(stmt, calls);
+            }
+        }
+        NodeData::If(_, body, else_node) => {
+            super_calls(body, calls);
+            if let Some(else_node) = else_node {
+                super_calls(else_node, calls);
+            }
+        }
+        NodeData::FunctionDeclaration(_, _, _, body, _)
+        | NodeData::Else(body)
+        | NodeData::While(_, body)
+        | NodeData::Loop(body)
+        | NodeData::Repeat(_, body)
+        | NodeData::DoUntil(body, _)
+        | NodeData::For(_, _, body) => super_calls(body, calls),
+        _ => {}
+    }
+}
+
+/// Makes the positions of an event's tokens relative to the whole object file.
+fn shift_lines(tokens: &mut [Token], offset: usize) {
+    for tk in tokens {
+        tk.text_range.start.line += offset;
+        tk.text_range.end.line += offset;
+    }
 }
@@ -0,0 +1,156 @@
+use std::{fs, path::PathBuf};
+
+use crate::{
+    checker::{
+        resolver::{resolve_script, ResolveError},
+        type_check::{check_types, TypeError},
+    },
+    gamemaker::resource::is_asset_name,
+    parser::ParseError,
+    tokenizer::{tokenize, Token, TokenizeError},
+};
+
+use super::{info::ProjectInfo, object::super_calls, source::Source, OrganizeError};
+
+/// A source file of the `scripts` directory. Its functions can be called from
+/// anywhere in the project.
+#[derive(Debug)]
+pub struct Script {
+    /// Name of the file without its extension, which names the script asset.
+    pub id: String,
+    /// File the script was read from.
+    pub path: PathBuf,
+    pub tokens: Vec<Token>,
+}
+
+/// A script along with the tree parsed from it.
+#[derive(Debug)]
+pub struct ParsedScript<'p> {
+    pub script: &'p Script,
+    pub source: Source<'p>,
+}
+
+impl<'p> ParsedScript<'p> {
+    pub fn parse(script: &'p Script) -> ParsedScript<'p> {
+        ParsedScript {
+            script,
+            source: Source::parse(&script.tokens),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ScriptParseError {
+    /// The file name can't be used as the name of a GameMaker asset.
+    InvalidName(String),
+    /// A `super();`, which only means something inside an object, and its line.
+    SuperOutsideObject(usize),
+    TokenizeError(TokenizeError),
+    ParseError(ParseError),
+    ResolveError(ResolveError),
+    TypeError(Box<TypeError>),
+}
+
+impl From<TokenizeError> for ScriptParseError {
+    fn from(value: TokenizeError) -> Self {
+        ScriptParseError::TokenizeError(value)
+    }
+}
+
+impl From<ParseError> for ScriptParseError {
+    fn from(value: ParseError) -> Self {
+        ScriptParseError::ParseError(value)
+    }
+}
+
+impl From<ResolveError> for ScriptParseError {
+    fn from(value: ResolveError) -> Self {
+        ScriptParseError::ResolveError(value)
+    }
+}
+
+impl From<TypeError> for ScriptParseError {
+    fn from(value: TypeError) -> Self {
+        ScriptParseError::TypeError(Box::new(value))
+    }
+}
+
+/// Reads every file of the `scripts` directory. Names and types are checked
+/// later by `check_script`, once every object and script is known.
+pub fn organize_scripts(path: PathBuf) -> Result<Vec<Script>, OrganizeError> {
+    let entries = path
+        .read_dir()
+        .map_err(OrganizeError::FailedReadingFromFileSystem)?;
+    let mut scripts: Vec<Script> = Vec::new();
+    let mut errors: Vec<(PathBuf, ScriptParseError)> = Vec::new();
+
+    for entry in entries {
+        let entry = entry.map_err(OrganizeError::FailedReadingFromFileSystem)?;
+        let path = entry.path();
+        if !path.is_file() {
+            return Err(OrganizeError::UnexpectedFile(path));
+        }
+
+        let id = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+        if !is_asset_name(&id) {
+            errors.push((path, ScriptParseError::InvalidName(id)));
+            continue;
+        }
+
+        let src = fs::read_to_string(&path).map_err(OrganizeError::FailedReadingFromFileSystem)?;
+        match tokenize(&src) {
+            Ok(tokens) => scripts.push(Script { id, path, tokens }),
+            Err(err) => errors.push((path, err.into())),
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(OrganizeError::ScriptParseErrors(errors));
+    }
+
+    // Directories aren't read in any particular order.
+    scripts.sort_by(|a, b| a.id.cmp(&b.id));
+
+    Ok(scripts)
+}
+
+/// Checks the names and types of a script.
+pub fn check_script(parsed: &ParsedScript, info: &ProjectInfo) -> Vec<ScriptParseError> {
+    let ParsedScript { script, source } = parsed;
+    let Source {
+        program,
+        parse_errors,
+    } = source;
+    if !parse_errors.is_empty() {
+        return parse_errors
+            .iter()
+            .cloned()
+            .map(ScriptParseError::from)
+            .collect();
+    }
+
+    let mut calls = Vec::new();
+    super_calls(program, &mut calls);
+    if !calls.is_empty() {
+        return calls
+            .iter()
+            .map(|call| ScriptParseError::SuperOutsideObject(call.text_range.start.line))
+            .collect();
+    }
+
+    let (_, resolve_errors) = resolve_script(program, info, &script.path);
+    let mut errors: Vec<ScriptParseError> = resolve_errors
+        .into_iter()
+        .map(ScriptParseError::from)
+        .collect();
+    errors.extend(
+        check_types(program, info, None)
+            .into_iter()
+            .map(ScriptParseError::from),
+    );
+
+    errors
+}
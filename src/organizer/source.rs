@@ -0,0 +1,25 @@
+use crate::{
+    ast::Node,
+    parser::{parse_tks, ParseError},
+    tokenizer::Token,
+};
+
+/// Code of an event or a script, parsed once from the tokens the project owns
+/// so the symbol table, the checks and the compiler all use the same tree.
+#[derive(Debug)]
+pub struct Source<'p> {
+    /// Has an `Error` node wherever a statement failed to parse.
+    pub program: Node<'p>,
+    pub parse_errors: Vec<ParseError>,
+}
+
+impl<'p> Source<'p> {
+    pub fn parse(tokens: &'p [Token]) -> Source<'p> {
+        let (program, parse_errors) = parse_tks(tokens);
+
+        Source {
+            program,
+            parse_errors,
+        }
+    }
+}
@@ -1,10 +1,11 @@
-use stmt::parse_stmt;
+use stmt::{check_loop_control, check_returns, parse_stmt};
+use types::TypeParsingError;
 use utils::delimiter_checker::DelimiterCheckerError;
 
 use crate::{
     ast::{Node, NodeData},
     text_data::{BorrowedTextRange, TextRange},
-    tokenizer::{tokenize, Token, TokenData, TokenizeError},
+    tokenizer::{Token, TokenData, TokenizeError},
     Boxxable,
 };
 
@@ -13,7 +14,7 @@ pub mod stmt;
 pub mod types;
 pub mod utils;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ParseError {
     pub data: ParseErrorData,
     pub text_range: TextRange,
@@ -32,7 +33,7 @@ impl ParseError {
             text_range: token.text_range,
         }
     }
-    fn new_unexpected_eof<'a, T: Into<TextRange>>(range: T) -> ParseError {
+    fn new_unexpected_eof<T: Into<TextRange>>(range: T) -> ParseError {
         ParseError {
             data: ParseErrorData::UnexpectedEOF,
             text_range: range.into(),
@@ -50,7 +51,7 @@ impl From<DelimiterCheckerError<'_>> for ParseError {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum ParseErrorData {
     UnexpectedToken(TokenData),
     MissingSemilicon,
@@ -58,9 +59,30 @@ pub enum ParseErrorData {
     UnclosedParenthesis,
     UnclosedCurly,
     UnexpectedEOF,
+    ExpectedExpression,
+    ExpectedIdentifier,
+    ExpectedBlock,
+    UnexpectedStatementStart(TokenData),
+    InvalidAssignmentTarget,
+    NotAStatement,
+    ExpectedUntil,
+    ExpectedIn,
+    /// A range with a `.step(0)`, which would never reach its end.
+    ZeroStep,
+    InvalidType,
+    MissingReturn,
+    MissingReturnValue,
+    BreakOutsideLoop,
+    ContinueOutsideLoop,
     TokenizeError(TokenizeError),
 }
 
+impl From<TypeParsingError<'_>> for ParseError {
+    fn from(value: TypeParsingError) -> Self {
+        ParseError::new(ParseErrorData::InvalidType, value.text_data.into())
+    }
+}
+
 impl From<TokenizeError> for ParseError {
     fn from(value: TokenizeError) -> Self {
         let text_range = value.text_range.clone();
@@ -68,26 +90,95 @@ impl From<TokenizeError> for ParseError {
     }
 }
 
-pub fn parse_tks<'a>(tokens: &'a [Token]) -> Result<Node<'a>, ParseError> {
+/// Doc comments before anything but a declaration are plain comments.
+fn attach_doc_comment<'a>(node: &mut Node<'a>, doc_tokens: &'a [Token]) {
+    let doc = match &mut *node.data {
+        NodeData::FunctionDeclaration(_, _, _, _, doc) => doc,
+        NodeData::VariableDeclaration(_, _, _, _, doc) => doc,
+        _ => return,
+    };
+
+    for tk in doc_tokens {
+        if let TokenData::DocComment(txt) = &tk.data {
+            doc.push(txt);
+        }
+    }
+}
+
+/// Tokens to skip after a statement failed to parse. Skips up to and including
+/// the next `;` or the `}` closing a block opened on the way, or up to the next
+/// statement keyword.
+fn recovery_point(tokens: &[Token]) -> usize {
+    let mut depth: usize = 0;
+
+    for (i, tk) in tokens.iter().enumerate() {
+        match tk.data {
+            TokenData::OpenCurly => depth += 1,
+            TokenData::CloseCurly if depth <= 1 => return i + 1,
+            TokenData::CloseCurly => depth -= 1,
+            TokenData::Semilicon if depth == 0 => return i + 1,
+            _ if i > 0 && depth == 0 && tk.data.is_statement_keyword() => return i,
+            _ => {}
+        }
+    }
+
+    tokens.len()
+}
+
+/// Parses every statement in `tokens`. A statement that fails to parse becomes a
+/// `NodeData::Error` node and parsing resumes after it, so the program is only
+/// partial when errors are returned.
+pub fn parse_tks<'a>(tokens: &'a [Token]) -> (Node<'a>, Vec<ParseError>) {
+    let mut errors: Vec<ParseError> = Vec::new();
+    let program = parse_block(tokens, &mut errors);
+    check_loop_control(&program, false, &mut errors);
+    check_returns(&program, &mut errors);
+
+    (program, errors)
+}
+
+pub fn parse_block<'a>(tokens: &'a [Token], errors: &mut Vec<ParseError>) -> Node<'a> {
     let mut statements: Vec<Node<'a>> = Vec::new();
     let mut ptr: usize = 0;
 
     while ptr < tokens.len() {
-        let (node, used) = parse_stmt(&tokens[ptr..])?;
+        let doc_start = ptr;
+        while let Some(TokenData::DocComment(_)) = tokens.get(ptr).map(|tk| &tk.data) {
+            ptr += 1;
+        }
 
-        assert_ne!(used, 0);
-        ptr += used;
+        if ptr == tokens.len() {
+            break;
+        }
+
+        match parse_stmt(&tokens[ptr..], errors) {
+            Ok((mut node, used)) => {
+                attach_doc_comment(&mut node, &tokens[doc_start..ptr]);
+                ptr += used;
+
+                statements.push(node);
+            }
+            Err(err) => {
+                errors.push(err);
 
-        statements.push(node);
+                let skipped = &tokens[ptr..ptr + recovery_point(&tokens[ptr..])];
+                ptr += skipped.len();
+
+                statements.push(Node {
+                    data: NodeData::Error.to_box(),
+                    text_range: BorrowedTextRange::from(skipped),
+                });
+            }
+        }
     }
 
-    let text_range = BorrowedTextRange::from((
-        &statements[0].text_range,
-        &statements.last().unwrap().text_range,
-    ));
+    let text_range = match (statements.first(), statements.last()) {
+        (Some(first), Some(last)) => BorrowedTextRange::from((&first.text_range, &last.text_range)),
+        _ => BorrowedTextRange::from(tokens),
+    };
 
-    Ok(Node {
+    Node {
         data: NodeData::Program(statements).to_box(),
         text_range,
-    })
+    }
 }
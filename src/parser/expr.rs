@@ -1,256 +1,488 @@
 use crate::{
-    ast::{Node, NodeData, OperatorType, PropertyAccessType},
-    parser::{utils::delimiter_checker::DelimiterChecker, BorrowedTextRange, ParseErrorData},
+    ast::{Node, NodeData, OperatorType, PropertyAccessType, RangeKind},
+    parser::{BorrowedTextRange, ParseErrorData},
     text_data::TextRange,
-    tokenizer::{Token, TokenData, TokensUtils},
+    tokenizer::{NumericValue, Token, TokenData},
     Boxxable,
 };
 
 use super::ParseError;
 
-pub fn parse_expr<'a>(tokens: &'a [Token]) -> Result<Node<'a>, ParseError> {
-    let mut tokens_to_parse: Vec<&[Token]> = Vec::new();
-    let mut operators: Vec<OperatorType> = Vec::new();
-    let mut prev_idx: usize = 0;
-    let mut delimiter_checker = DelimiterChecker::new();
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Associativity {
+    Left,
+    Right,
+}
 
-    if tokens.find_free(TokenData::Comma).is_some() {
-        return parse_tuple(tokens);
-    }
+#[derive(Debug, PartialEq)]
+pub enum PrefixOperator {
+    Neg,
+    Not,
+    BitNot,
+}
 
-    for (i, tk) in tokens.iter().enumerate() {
-        let is_operator = tk.data.operator_type().is_some() && i != 0;
-        delimiter_checker.check(tk)?;
-        
-        let at_end = i == tokens.len() - 1;
-        if (is_operator && delimiter_checker.is_free()) || at_end {
-            if is_operator {
-                operators.push(tk.data.operator_type().expect("Token wasnt an operator"));
-            }
+#[derive(Debug, PartialEq)]
+pub enum PostfixOperator {
+    Call,
+    Index,
+    Property(PropertyAccessType),
+}
 
-            let range = if at_end {
-                prev_idx..tokens.len()
-            } else {
-                prev_idx..i
-            };
-            tokens_to_parse.push(&tokens[range]);
-            prev_idx = i + 1;
+#[derive(Debug, PartialEq)]
+pub enum OperatorKind {
+    Prefix(PrefixOperator),
+    Infix(OperatorType),
+    Postfix(PostfixOperator),
+    /// Only parsed in `for` headers, see `parse_iterable`.
+    Range(RangeKind),
+}
+
+pub struct OperatorRule {
+    pub token: TokenData,
+    pub kind: OperatorKind,
+    pub associativity: Associativity,
+}
+
+const RANGE_PRECEDENCE: u8 = 0;
+const PREFIX_PRECEDENCE: u8 = 12;
+const POSTFIX_PRECEDENCE: u8 = 13;
+
+impl OperatorRule {
+    /// Binding power of the rule, higher binds tighter. Infix operators take
+    /// theirs from `OperatorType::precedence` so the GML backend agrees with the parser.
+    pub fn precedence(&self) -> u8 {
+        match &self.kind {
+            OperatorKind::Prefix(_) => PREFIX_PRECEDENCE,
+            OperatorKind::Infix(op) => op.precedence(),
+            OperatorKind::Postfix(_) => POSTFIX_PRECEDENCE,
+            OperatorKind::Range(_) => RANGE_PRECEDENCE,
         }
     }
+}
+
+macro_rules! rule {
+    ($token:ident, $kind:expr) => {
+        rule!($token, $kind, Associativity::Left)
+    };
+    ($token:ident, $kind:expr, $associativity:expr) => {
+        OperatorRule {
+            token: TokenData::$token,
+            kind: $kind,
+            associativity: $associativity,
+        }
+    };
+}
 
-    let nodes: Result<Vec<Node>, ParseError> = tokens_to_parse
-        .into_iter()
-        .map(|tks| parse_expr_component(tks))
-        .collect();
-    let mut nodes = nodes?;
-    parse_operators(
-        &mut nodes,
-        &mut operators,
-        &[OperatorType::Mul, OperatorType::Div],
-    );
-    parse_operators(
-        &mut nodes,
-        &mut operators,
-        &[OperatorType::Add, OperatorType::Sub],
-    );
-    parse_operators(
-        &mut nodes,
-        &mut operators,
-        &[OperatorType::NotEquals, OperatorType::IsEquals],
-    );
-    parse_operators(&mut nodes, &mut operators, &[OperatorType::Or]);
-
-    assert_eq!(nodes.len(), 1);
-
-    Ok(nodes.remove(0))
+/// Every operator the expression parser knows about. Parsed `BinaryExpr`s
+/// borrow their operator from here.
+pub const OPERATOR_TABLE: &[OperatorRule] = &[
+    rule!(
+        Sub,
+        OperatorKind::Prefix(PrefixOperator::Neg),
+        Associativity::Right
+    ),
+    rule!(
+        Not,
+        OperatorKind::Prefix(PrefixOperator::Not),
+        Associativity::Right
+    ),
+    rule!(
+        Tilde,
+        OperatorKind::Prefix(PrefixOperator::BitNot),
+        Associativity::Right
+    ),
+    rule!(DotDot, OperatorKind::Range(RangeKind::Exclusive)),
+    rule!(DotDotEquals, OperatorKind::Range(RangeKind::Inclusive)),
+    rule!(Or, OperatorKind::Infix(OperatorType::Or)),
+    rule!(Xor, OperatorKind::Infix(OperatorType::Xor)),
+    rule!(And, OperatorKind::Infix(OperatorType::And)),
+    rule!(IsEquals, OperatorKind::Infix(OperatorType::IsEquals)),
+    rule!(NotEquals, OperatorKind::Infix(OperatorType::NotEquals)),
+    rule!(LessThan, OperatorKind::Infix(OperatorType::LessThan)),
+    rule!(GreaterThan, OperatorKind::Infix(OperatorType::GreaterThan)),
+    rule!(
+        LessOrEquals,
+        OperatorKind::Infix(OperatorType::LessOrEquals)
+    ),
+    rule!(
+        GreaterOrEquals,
+        OperatorKind::Infix(OperatorType::GreaterOrEquals)
+    ),
+    rule!(Pipe, OperatorKind::Infix(OperatorType::BitOr)),
+    rule!(Caret, OperatorKind::Infix(OperatorType::BitXor)),
+    rule!(Ampersand, OperatorKind::Infix(OperatorType::BitAnd)),
+    rule!(ShiftLeft, OperatorKind::Infix(OperatorType::ShiftLeft)),
+    rule!(ShiftRight, OperatorKind::Infix(OperatorType::ShiftRight)),
+    rule!(Add, OperatorKind::Infix(OperatorType::Add)),
+    rule!(Sub, OperatorKind::Infix(OperatorType::Sub)),
+    rule!(Mul, OperatorKind::Infix(OperatorType::Mul)),
+    rule!(Div, OperatorKind::Infix(OperatorType::Div)),
+    rule!(IntDiv, OperatorKind::Infix(OperatorType::IntDiv)),
+    rule!(Percent, OperatorKind::Infix(OperatorType::Mod)),
+    rule!(
+        OpenParenthesis,
+        OperatorKind::Postfix(PostfixOperator::Call)
+    ),
+    rule!(OpenBracket, OperatorKind::Postfix(PostfixOperator::Index)),
+    rule!(
+        Dot,
+        OperatorKind::Postfix(PostfixOperator::Property(PropertyAccessType::Struct))
+    ),
+    rule!(
+        ModAccess,
+        OperatorKind::Postfix(PostfixOperator::Property(PropertyAccessType::Mod))
+    ),
+];
+
+fn find_prefix_rule(tk: &TokenData) -> Option<&'static OperatorRule> {
+    OPERATOR_TABLE
+        .iter()
+        .find(|rule| rule.token == *tk && matches!(rule.kind, OperatorKind::Prefix(_)))
 }
 
-fn parse_operators<'a>(
-    nodes: &mut Vec<Node<'a>>,
-    operators: &mut Vec<OperatorType>,
-    operators_to_parse: &'a [OperatorType],
-) {
-    assert_eq!(operators.len(), nodes.len() - 1);
-
-    let mut ptr: usize = 0;
-
-    while ptr < operators.len() {
-        let operator = &operators[ptr];
-        let current_operator = operators_to_parse
-            .into_iter()
-            .find(|optype| **optype == *operator);
-        if let Some(op) = current_operator {
-            let left = nodes.remove(ptr);
-            let right = nodes.remove(ptr);
-            let text_range = BorrowedTextRange::from((&left.text_range, &right.text_range));
-            let node_data = NodeData::BinaryExpr(left, op, right);
-
-            let node = Node {
-                text_range,
-                data: node_data.to_box(),
-            };
+fn find_operand_rule(tk: &TokenData) -> Option<&'static OperatorRule> {
+    OPERATOR_TABLE
+        .iter()
+        .find(|rule| rule.token == *tk && !matches!(rule.kind, OperatorKind::Prefix(_)))
+}
+
+/// Parses the whole slice as one expression. A top level comma makes a tuple.
+pub fn parse_expr<'a>(tokens: &'a [Token]) -> Result<Node<'a>, ParseError> {
+    ExprParser::new(tokens, false).parse_all()
+}
+
+/// Like `parse_expr`, but also allows a range like `0..n` or `(0..=n).step(2)`,
+/// which only make sense as what a `for` loop iterates over.
+pub fn parse_iterable<'a>(tokens: &'a [Token]) -> Result<Node<'a>, ParseError> {
+    ExprParser::new(tokens, true).parse_all()
+}
+
+struct ExprParser<'a> {
+    tokens: &'a [Token],
+    ptr: usize,
+    /// Ranges are only allowed as the whole iterable of a `for`, optionally in
+    /// parentheses, and never as an operand.
+    allow_range: bool,
+}
 
-            nodes.insert(ptr, node);
-            operators.remove(ptr);
-        } else {
-            ptr += 1;
+impl<'a> ExprParser<'a> {
+    fn new(tokens: &'a [Token], allow_range: bool) -> Self {
+        ExprParser {
+            tokens,
+            ptr: 0,
+            allow_range,
         }
     }
-}
 
-// TODO: Better Error Handling
-fn parse_expr_component(tokens: &[Token]) -> Result<Node, ParseError> {
-    assert_ne!(tokens.len(), 0);
+    fn parse_all(&mut self) -> Result<Node<'a>, ParseError> {
+        let node = self.parse_tuple_or_expr()?;
 
-    if tokens.len() == 1 {
-        return parse_primary(&tokens[0]);
+        match self.peek() {
+            Some(tk) => Err(ParseError::new_unexpected_token(tk.clone())),
+            None => Ok(node),
+        }
     }
 
-    if let TokenData::Sub = tokens[0].data {
-        let text_range = BorrowedTextRange::from(tokens);
-        let data = NodeData::Neg(parse_expr(&tokens[1..])?).to_box();
-        return Ok(Node { text_range, data });
+    fn peek(&self) -> Option<&'a Token> {
+        self.tokens.get(self.ptr)
     }
 
-    if let TokenData::OpenParenthesis = tokens[0].data {
-        if tokens
-            .find_pair(0)
-            .is_some_and(|idx| idx == tokens.len() - 1)
-        {
-            return parse_expr(&tokens[1..tokens.len() - 1]);
-        }
+    fn next(&mut self) -> Result<&'a Token, ParseError> {
+        let tk = self
+            .tokens
+            .get(self.ptr)
+            .ok_or_else(|| self.unexpected_eof())?;
+        self.ptr += 1;
+
+        Ok(tk)
     }
 
-    if tokens.find_free(&TokenData::Comma).is_some() {
-        return parse_tuple(tokens);
+    /// `data` reported at the last token, for when the slice ends too early.
+    fn expected(&self, data: ParseErrorData) -> ParseError {
+        let text_range = self
+            .tokens
+            .last()
+            .map_or_else(TextRange::new_empty, |tk| tk.text_range.clone());
+
+        ParseError::new(data, text_range)
     }
 
-    if let TokenData::Identifier(_) = &tokens[0].data {
-        let node = parse_primary(&tokens[0])?;
-        return parse_chain(node, &tokens[1..]);
+    fn unexpected_eof(&self) -> ParseError {
+        match self.tokens.last() {
+            Some(tk) => ParseError::new_unexpected_eof(tk),
+            None => ParseError::new_unexpected_eof(TextRange::new_empty()),
+        }
     }
 
-    todo!()
-}
+    fn expect(
+        &mut self,
+        expected: TokenData,
+        error: ParseErrorData,
+    ) -> Result<&'a Token, ParseError> {
+        match self.peek() {
+            Some(tk) if tk.data == expected => self.next(),
+            Some(tk) => Err(ParseError::new_unexpected_token(tk.clone())),
+            None => {
+                let text_range = TextRange::from(self.tokens);
+                Err(ParseError::new(error, text_range))
+            }
+        }
+    }
 
-fn parse_primary(tk: &Token) -> Result<Node, ParseError> {
-    let text_range = BorrowedTextRange::from(&tk.text_range);
-    let data = match &tk.data {
-        TokenData::Identifier(id) => NodeData::Identifier(&id),
-        TokenData::NumericLiteral(num) => NodeData::NumericLiteral(&num),
-        _ => panic!("UNEXPECTED: {:?}", tk),
-    };
+    fn parse_tuple_or_expr(&mut self) -> Result<Node<'a>, ParseError> {
+        let first = self.parse_expr_bp(0)?;
 
-    Ok(Node {
-        text_range,
-        data: data.to_box(),
-    })
-}
+        if !matches!(self.peek().map(|tk| &tk.data), Some(TokenData::Comma)) {
+            return Ok(first);
+        }
 
-fn parse_function_call<'a>(
-    func_node: Node<'a>,
-    tokens: &'a [Token],
-) -> Result<Node<'a>, ParseError> {
-    assert!(tokens.len() > 1);
-    assert!(matches!(tokens[0].data, TokenData::OpenParenthesis));
+        // A range is iterated over on its own, never as part of a tuple.
+        if let (NodeData::Range(..), Some(comma)) = (&*first.data, self.peek()) {
+            return Err(ParseError::new_unexpected_token(comma.clone()));
+        }
 
-    let close_paren = tokens.find_pair(0).unwrap();
+        let mut values = vec![first];
+        while let Some(TokenData::Comma) = self.peek().map(|tk| &tk.data) {
+            self.ptr += 1;
+            values.push(self.parse_expr_without_range(0)?);
+        }
 
-    let args_tks = &tokens[1..close_paren].split_tks(&TokenData::Comma);
-    let args_nodes: Result<Vec<Node>, ParseError> =
-        args_tks.into_iter().map(|tks| parse_expr(tks)).collect();
+        let text_range =
+            BorrowedTextRange::from((&values[0].text_range, &values[values.len() - 1].text_range));
+        let data = NodeData::Tuple(values).to_box();
 
-    let text_range = BorrowedTextRange::from(tokens);
-    let data = NodeData::FunctionCall(func_node, args_nodes?);
-    let node = Node {
-        text_range,
-        data: data.to_box(),
-    };
+        Ok(Node { data, text_range })
+    }
 
-    if close_paren < tokens.len() - 1 {
-        parse_chain(node, &tokens[close_paren + 1..])
-    } else {
-        Ok(node)
+    /// Parses an operand of another operator, where a range can't appear.
+    fn parse_expr_without_range(&mut self, min_precedence: u8) -> Result<Node<'a>, ParseError> {
+        let allow_range = std::mem::replace(&mut self.allow_range, false);
+        let node = self.parse_expr_bp(min_precedence);
+        self.allow_range = allow_range;
+
+        node
     }
-}
 
-fn parse_property_access<'a>(
-    struct_node: Node<'a>,
-    tokens: &'a [Token],
-    property_access_type: PropertyAccessType,
-) -> Result<Node<'a>, ParseError> {
-    assert!(tokens[0].data.property_access_type().is_some());
-    if tokens.get(1).is_none() {
-        return Err(ParseError::new_unexpected_token(tokens[0].clone()));
+    fn parse_expr_bp(&mut self, min_precedence: u8) -> Result<Node<'a>, ParseError> {
+        let mut lhs = self.parse_prefix()?;
+
+        while let Some(tk) = self.peek() {
+            let Some(rule) = find_operand_rule(&tk.data) else {
+                break;
+            };
+
+            // Nothing but one `.step(...)` can follow a range.
+            if let NodeData::Range(..) = &*lhs.data {
+                if !self.is_range_step(&lhs) {
+                    return Err(ParseError::new_unexpected_token(tk.clone()));
+                }
+                lhs = self.parse_range_step(lhs)?;
+                continue;
+            }
+
+            let precedence = rule.precedence();
+            if precedence < min_precedence {
+                break;
+            }
+
+            lhs = match &rule.kind {
+                OperatorKind::Postfix(postfix) => self.parse_postfix(lhs, postfix)?,
+                OperatorKind::Range(kind) => {
+                    if !self.allow_range {
+                        break;
+                    }
+
+                    self.ptr += 1;
+                    let end = self.parse_expr_without_range(precedence + 1)?;
+
+                    let text_range = BorrowedTextRange::from((&lhs.text_range, &end.text_range));
+                    let data = NodeData::Range(lhs, end, *kind, None).to_box();
+                    Node { data, text_range }
+                }
+                OperatorKind::Infix(op) => {
+                    self.ptr += 1;
+                    let next_min_precedence = match rule.associativity {
+                        Associativity::Left => precedence + 1,
+                        Associativity::Right => precedence,
+                    };
+                    let rhs = self.parse_expr_without_range(next_min_precedence)?;
+
+                    let text_range = BorrowedTextRange::from((&lhs.text_range, &rhs.text_range));
+                    let data = NodeData::BinaryExpr(lhs, op, rhs).to_box();
+                    Node { data, text_range }
+                }
+                OperatorKind::Prefix(_) => break,
+            };
+        }
+
+        Ok(lhs)
     }
 
-    if let TokenData::Identifier(_) = &tokens[1].data {
-        let text_range = BorrowedTextRange::from((
-            &struct_node.text_range,
-            &BorrowedTextRange::from(&tokens[0..2]),
-        ));
-        let prop_node = parse_expr(&tokens[1..=1])?;
-        let data = match property_access_type {
-            PropertyAccessType::Struct => NodeData::StructAccess(struct_node, prop_node),
-            PropertyAccessType::Mod => NodeData::ModAccess(struct_node, prop_node),
-        };
-        let node = Node {
-            data: data.to_box(),
-            text_range,
-        };
-        if tokens.len() == 2 {
-            Ok(node)
-        } else {
-            parse_chain(node, &tokens[2..])
+    fn parse_prefix(&mut self) -> Result<Node<'a>, ParseError> {
+        if self.peek().is_none() {
+            return Err(self.expected(ParseErrorData::ExpectedExpression));
+        }
+        let tk = self.next()?;
+
+        if let Some(rule) = find_prefix_rule(&tk.data) {
+            let expr = self.parse_expr_without_range(rule.precedence())?;
+            let text_range =
+                BorrowedTextRange::from((&BorrowedTextRange::from(tk), &expr.text_range));
+            let data = match rule.kind {
+                OperatorKind::Prefix(PrefixOperator::Not) => NodeData::Not(expr),
+                OperatorKind::Prefix(PrefixOperator::BitNot) => NodeData::BitNot(expr),
+                _ => NodeData::Neg(expr),
+            };
+
+            return Ok(Node {
+                text_range,
+                data: data.to_box(),
+            });
         }
-    } else {
-        Err(ParseError::new_unexpected_token(tokens[1].clone()))
+
+        if let TokenData::OpenParenthesis = tk.data {
+            let node = self.parse_tuple_or_expr()?;
+            self.expect(
+                TokenData::CloseParenthesis,
+                ParseErrorData::UnclosedParenthesis,
+            )?;
+
+            return Ok(node);
+        }
+
+        parse_primary(tk)
     }
-}
 
-fn parse_array_access<'a>(arr_node: Node<'a>, tokens: &'a [Token]) -> Result<Node<'a>, ParseError> {
-    assert_eq!(tokens[0].data, TokenData::OpenBracket);
+    /// Whether the next tokens are `.step(` right after a range without a step.
+    fn is_range_step(&self, lhs: &Node) -> bool {
+        let next: Vec<&TokenData> = self.tokens[self.ptr..]
+            .iter()
+            .take(3)
+            .map(|tk| &tk.data)
+            .collect();
+
+        matches!(*lhs.data, NodeData::Range(_, _, _, None))
+            && matches!(
+                next[..],
+                [TokenData::Dot, TokenData::Identifier(id), TokenData::OpenParenthesis] if id == "step"
+            )
+    }
 
-    let end_brack = tokens.find_pair(0).ok_or_else(|| {
-        let text_range: TextRange = BorrowedTextRange::from(tokens).into();
-        ParseError::new(ParseErrorData::UnclosedBracket, text_range)
-    })?;
+    fn parse_range_step(&mut self, mut range: Node<'a>) -> Result<Node<'a>, ParseError> {
+        self.ptr += 3;
+        let allow_range = std::mem::replace(&mut self.allow_range, false);
+        let step_node = self.parse_expr_bp(0)?;
+        let end = self.expect(
+            TokenData::CloseParenthesis,
+            ParseErrorData::UnclosedParenthesis,
+        )?;
+        self.allow_range = allow_range;
+
+        if is_zero(&step_node) {
+            let text_range = step_node.text_range.clone().into();
+            return Err(ParseError::new(ParseErrorData::ZeroStep, text_range));
+        }
 
-    let idx_node = parse_expr(&tokens[1..end_brack])?;
+        if let NodeData::Range(_, _, _, step) = &mut *range.data {
+            *step = Some(step_node);
+        }
+        range.text_range.end = &end.text_range.end;
 
-    let text_range = BorrowedTextRange::from((
-        &arr_node.text_range,
-        &BorrowedTextRange::from(&tokens[0..=end_brack]),
-    ));
-    let data = NodeData::ArrayAccess(arr_node, idx_node).to_box();
-    let node = Node { data, text_range };
+        Ok(range)
+    }
+
+    fn parse_postfix(
+        &mut self,
+        lhs: Node<'a>,
+        postfix: &PostfixOperator,
+    ) -> Result<Node<'a>, ParseError> {
+        self.ptr += 1;
+        let allow_range = std::mem::replace(&mut self.allow_range, false);
+        let start = lhs.text_range.start;
+
+        let (data, end) = match postfix {
+            PostfixOperator::Call => {
+                let mut args: Vec<Node<'a>> = Vec::new();
+
+                if !matches!(
+                    self.peek().map(|tk| &tk.data),
+                    Some(TokenData::CloseParenthesis)
+                ) {
+                    args.push(self.parse_expr_bp(0)?);
+                    while let Some(TokenData::Comma) = self.peek().map(|tk| &tk.data) {
+                        self.ptr += 1;
+                        args.push(self.parse_expr_bp(0)?);
+                    }
+                }
+
+                let end = self.expect(
+                    TokenData::CloseParenthesis,
+                    ParseErrorData::UnclosedParenthesis,
+                )?;
+                (NodeData::FunctionCall(lhs, args), end)
+            }
+            PostfixOperator::Index => {
+                let idx_node = self.parse_tuple_or_expr()?;
+                let end = self.expect(TokenData::CloseBracket, ParseErrorData::UnclosedBracket)?;
+                (NodeData::ArrayAccess(lhs, idx_node), end)
+            }
+            PostfixOperator::Property(property_access_type) => {
+                let Some(prop) = self.peek() else {
+                    return Err(self.expected(ParseErrorData::ExpectedIdentifier));
+                };
+                let TokenData::Identifier(_) = prop.data else {
+                    return Err(ParseError::new(
+                        ParseErrorData::ExpectedIdentifier,
+                        prop.text_range.clone(),
+                    ));
+                };
+                self.ptr += 1;
+                let prop_node = parse_primary(prop)?;
+
+                let data = match property_access_type {
+                    PropertyAccessType::Struct => NodeData::StructAccess(lhs, prop_node),
+                    PropertyAccessType::Mod => NodeData::ModAccess(lhs, prop_node),
+                };
+                (data, prop)
+            }
+        };
 
-    if end_brack == tokens.len() - 1 {
-        Ok(node)
-    } else {
-        parse_chain(node, &tokens[end_brack + 1..])
+        let text_range = BorrowedTextRange {
+            start,
+            end: &end.text_range.end,
+        };
+        self.allow_range = allow_range;
+
+        Ok(Node {
+            text_range,
+            data: data.to_box(),
+        })
     }
 }
 
-fn parse_chain<'a>(node: Node<'a>, tokens: &'a [Token]) -> Result<Node<'a>, ParseError> {
-    assert!(tokens.len() > 1);
-    match tokens[0].data {
-        TokenData::OpenParenthesis => parse_function_call(node, tokens),
-        TokenData::Dot => parse_property_access(node, tokens, PropertyAccessType::Struct),
-        TokenData::ModAccess => parse_property_access(node, tokens, PropertyAccessType::Mod),
-        TokenData::OpenBracket => parse_array_access(node, tokens),
-        _ => todo!(),
+/// Whether `node` is a literal `0`, possibly negated.
+fn is_zero(node: &Node) -> bool {
+    match &*node.data {
+        NodeData::NumericLiteral(num) => match num.value {
+            NumericValue::Integer(value) => value == 0,
+            NumericValue::Real(value) => value == 0.0,
+        },
+        NodeData::Neg(value) => is_zero(value),
+        _ => false,
     }
 }
 
-fn parse_tuple<'a>(tokens: &'a [Token]) -> Result<Node<'a>, ParseError> {
-    let nodes: Result<Vec<Node<'a>>, ParseError> = tokens
-        .split_tks(&TokenData::Comma)
-        .iter()
-        .map(|tks| parse_expr(tks))
-        .collect();
-    let text_range = BorrowedTextRange::from(tokens);
-    let data = NodeData::Tuple(nodes?).to_box();
+fn parse_primary(tk: &Token) -> Result<Node<'_>, ParseError> {
+    let text_range = BorrowedTextRange::from(&tk.text_range);
+    let data = match &tk.data {
+        TokenData::Identifier(id) => NodeData::Identifier(id),
+        TokenData::NumericLiteral(num) => NodeData::NumericLiteral(num),
+        TokenData::StringLiteral(txt) => NodeData::String(txt),
+        _ => return Err(ParseError::new_unexpected_token(tk.clone())),
+    };
 
-    Ok(Node { data, text_range })
+    Ok(Node {
+        text_range,
+        data: data.to_box(),
+    })
 }
@@ -1,6 +1,14 @@
 use crate::{
-    ast::{DeclarationType, Node, NodeData, VariableModificationType},
-    parser::{expr::parse_expr, parse_tks, utils::parse_parameters, ParseErrorData},
+    ast::{DeclarationType, Node, NodeData},
+    parser::{
+        expr::{parse_expr, parse_iterable},
+        parse_block,
+        utils::{
+            delimiter_checker::DelimiterChecker, parse_identifier, parse_parameters, parse_type,
+            parse_type_annotation,
+        },
+        ParseErrorData,
+    },
     text_data::{BorrowedTextRange, TextRange},
     tokenizer::{Token, TokenData, TokensUtils},
     Boxxable,
@@ -8,12 +16,83 @@ use crate::{
 
 use super::ParseError;
 
-pub fn parse_stmt<'a>(tokens: &'a [Token]) -> Result<(Node<'a>, usize), ParseError> {
-    if matches!(&tokens[0].data, TokenData::Const | TokenData::Var | TokenData::Let) {
-        let semilicon = tokens.find_free(&TokenData::Semilicon).ok_or_else(|| {
-            let text_data = BorrowedTextRange::from(tokens);
-            ParseError::new(ParseErrorData::MissingSemilicon, text_data.into())
-        })?;
+/// `parse_expr` for the tokens following `before`, reporting a missing expression at `before`.
+fn parse_expr_after<'a>(before: &'a Token, tokens: &'a [Token]) -> Result<Node<'a>, ParseError> {
+    if tokens.is_empty() {
+        return Err(ParseError::new(
+            ParseErrorData::ExpectedExpression,
+            before.text_range.clone(),
+        ));
+    }
+
+    parse_expr(tokens)
+}
+
+/// Parses the `{ ... }` block opening at `tokens[start]`, returning its body and the
+/// index of the closing curly.
+fn parse_braced_block<'a>(
+    tokens: &'a [Token],
+    start: usize,
+    errors: &mut Vec<ParseError>,
+) -> Result<(Node<'a>, usize), ParseError> {
+    if !matches!(
+        tokens.get(start).map(|tk| &tk.data),
+        Some(TokenData::OpenCurly)
+    ) {
+        return Err(ParseError::new(
+            ParseErrorData::ExpectedBlock,
+            tokens[start - 1].text_range.clone(),
+        ));
+    }
+
+    let end_curly = tokens.find_pair(start).ok_or(ParseError::new(
+        ParseErrorData::UnclosedCurly,
+        TextRange::from(tokens),
+    ))?;
+    let body = parse_block(&tokens[start + 1..end_curly], errors);
+
+    Ok((body, end_curly))
+}
+
+/// Parses `keyword expr { ... }`, returning the expression, the body and the index
+/// of the closing curly.
+fn parse_headed_block<'a>(
+    tokens: &'a [Token],
+    errors: &mut Vec<ParseError>,
+) -> Result<(Node<'a>, Node<'a>, usize), ParseError> {
+    let start_curly = tokens
+        .find_free(TokenData::OpenCurly)
+        .ok_or(ParseError::new(
+            ParseErrorData::ExpectedBlock,
+            TextRange::from(tokens),
+        ))?;
+    let head = parse_expr_after(&tokens[0], &tokens[1..start_curly])?;
+    let (body, end_curly) = parse_braced_block(tokens, start_curly, errors)?;
+
+    Ok((head, body, end_curly))
+}
+
+/// Index of the semilicon ending the statement at the start of `tokens`.
+fn find_semilicon(tokens: &[Token]) -> Result<usize, ParseError> {
+    tokens.find_free(&TokenData::Semilicon).ok_or_else(|| {
+        let text_data = BorrowedTextRange::from(tokens);
+        ParseError::new(ParseErrorData::MissingSemilicon, text_data.into())
+    })
+}
+
+pub fn parse_stmt<'a>(
+    tokens: &'a [Token],
+    errors: &mut Vec<ParseError>,
+) -> Result<(Node<'a>, usize), ParseError> {
+    let Some(first) = tokens.first() else {
+        return Err(ParseError::new_unexpected_eof(tokens));
+    };
+
+    if matches!(
+        &first.data,
+        TokenData::Const | TokenData::Var | TokenData::Let
+    ) {
+        let semilicon = find_semilicon(tokens)?;
 
         return Ok((
             parse_variable_declaration(&tokens[0..=semilicon])?,
@@ -21,72 +100,136 @@ pub fn parse_stmt<'a>(tokens: &'a [Token]) -> Result<(Node<'a>, usize), ParseErr
         ));
     }
 
-    if let TokenData::Fn = tokens[0].data {
-        return parse_function_declaration(tokens);
+    if let TokenData::Fn = first.data {
+        return parse_function_declaration(tokens, errors);
     }
 
-    if let TokenData::If = tokens[0].data {
-        return parse_if(tokens);
+    if let TokenData::If = first.data {
+        return parse_if(tokens, errors);
     }
 
-    if let TokenData::Identifier(_) = tokens[0].data {
-        let semilicon = tokens.find_free(&TokenData::Semilicon).ok_or_else(|| {
-            let text_data = BorrowedTextRange::from(tokens);
-            ParseError::new(ParseErrorData::MissingSemilicon, text_data.into())
-        })?;
+    if matches!(
+        first.data,
+        TokenData::While | TokenData::Loop | TokenData::Repeat | TokenData::Do
+    ) {
+        return parse_loop(tokens, errors);
+    }
+
+    if let TokenData::Return = first.data {
+        let semilicon = find_semilicon(tokens)?;
+        let value = match semilicon {
+            1 => None,
+            _ => Some(parse_expr(&tokens[1..semilicon])?),
+        };
+        let text_range = BorrowedTextRange::from(&tokens[..=semilicon]);
+        let data = NodeData::Return(value).to_box();
+
+        return Ok((Node { data, text_range }, semilicon + 1));
+    }
+
+    if let TokenData::For = first.data {
+        return parse_for(tokens, errors);
+    }
+
+    if matches!(first.data, TokenData::Break | TokenData::Continue) {
+        let semilicon = find_semilicon(tokens)?;
+        if semilicon != 1 {
+            return Err(ParseError::new_unexpected_token(tokens[1].clone()));
+        }
+
+        let data = match first.data {
+            TokenData::Break => NodeData::Break,
+            _ => NodeData::Continue,
+        };
+        let text_range = BorrowedTextRange::from(&tokens[..=semilicon]);
 
         return Ok((
-            parse_variable_modification(&tokens[..=semilicon])?,
+            Node {
+                data: data.to_box(),
+                text_range,
+            },
             semilicon + 1,
         ));
     }
 
-    panic!()
+    if let TokenData::Super = first.data {
+        let semilicon = find_semilicon(tokens)?;
+        let found = &tokens[1..semilicon];
+        let expected = [TokenData::OpenParenthesis, TokenData::CloseParenthesis];
+        let unexpected = match found
+            .iter()
+            .zip(&expected)
+            .find(|(tk, data)| tk.data != **data)
+        {
+            Some((tk, _)) => Some(tk),
+            None if found.len() == expected.len() => None,
+            None => Some(found.get(expected.len()).unwrap_or(&tokens[semilicon])),
+        };
+        if let Some(tk) = unexpected {
+            return Err(ParseError::new_unexpected_token(tk.clone()));
+        }
+
+        let text_range = BorrowedTextRange::from(&tokens[..=semilicon]);
+
+        return Ok((
+            Node {
+                data: NodeData::Super.to_box(),
+                text_range,
+            },
+            semilicon + 1,
+        ));
+    }
+
+    if let TokenData::Identifier(_) = first.data {
+        let semilicon = find_semilicon(tokens)?;
+
+        return Ok((parse_expr_statement(&tokens[..=semilicon])?, semilicon + 1));
+    }
+
+    Err(ParseError::new(
+        ParseErrorData::UnexpectedStatementStart(first.data.clone()),
+        first.text_range.clone(),
+    ))
 }
 
+/// Parses `tokens`, which run up to and including the closing semilicon.
 fn parse_variable_declaration<'a>(tokens: &'a [Token]) -> Result<Node<'a>, ParseError> {
     let declaration_type = DeclarationType::try_from(&tokens[0])
         .map_err(|tk| ParseError::new_unexpected_token(tk.clone()))?;
-    assert!(matches!(
-        &tokens.last().expect("Tokens slice was empty").data,
-        TokenData::Semilicon
-    ));
+    let semilicon = tokens.len() - 1;
 
-    let identifier: Node = if let TokenData::Identifier(id) = &tokens[1].data {
-        Ok(Node {
-            data: NodeData::Identifier(id).to_box(),
-            text_range: BorrowedTextRange::from(&tokens[1].text_range),
-        })
-    } else {
-        Err(ParseError::new_unexpected_token(tokens[1].clone()))
-    }?;
+    let identifier = parse_identifier(&tokens[..semilicon], 1)?;
+    let (r#type, equals_idx) = parse_type_annotation(&tokens[..semilicon], 2)?;
 
-    if !matches!(tokens.get(2).map(|tk| &tk.data), Some(TokenData::Equals)) {
-        return Err(ParseError::new_unexpected_token(tokens[2].clone()));
+    let equals = &tokens[equals_idx];
+    if !matches!(equals.data, TokenData::Equals) {
+        return Err(ParseError::new_unexpected_token(equals.clone()));
     }
 
-    let expr = parse_expr(&tokens[3..tokens.len() - 1])?;
+    let expr = parse_expr_after(equals, &tokens[equals_idx + 1..semilicon])?;
 
     let text_range = BorrowedTextRange::from(tokens);
-    let data = NodeData::VariableDeclaration(declaration_type, identifier, expr).to_box();
+    let data =
+        NodeData::VariableDeclaration(declaration_type, identifier, r#type, expr, Vec::new())
+            .to_box();
     let node = Node { text_range, data };
 
     Ok(node)
 }
 
-fn parse_function_declaration<'a>(tokens: &'a [Token]) -> Result<(Node<'a>, usize), ParseError> {
-    assert!(matches!(tokens[0].data, TokenData::Fn));
-    let identifier: Node = if let TokenData::Identifier(id) = &tokens[1].data {
-        Ok(Node {
-            data: NodeData::Identifier(id).to_box(),
-            text_range: BorrowedTextRange::from(&tokens[1].text_range),
-        })
-    } else {
-        Err(ParseError::new_unexpected_token(tokens[1].clone()))
-    }?;
-
-    if !matches!(tokens[2].data, TokenData::OpenParenthesis) {
-        return Err(ParseError::new_unexpected_token(tokens[2].clone()));
+fn parse_function_declaration<'a>(
+    tokens: &'a [Token],
+    errors: &mut Vec<ParseError>,
+) -> Result<(Node<'a>, usize), ParseError> {
+    let identifier = parse_identifier(tokens, 1)?;
+
+    match tokens.get(2) {
+        Some(Token {
+            data: TokenData::OpenParenthesis,
+            ..
+        }) => {}
+        Some(tk) => return Err(ParseError::new_unexpected_token(tk.clone())),
+        None => return Err(ParseError::new_unexpected_eof(tokens)),
     }
 
     let end_parenthesis = tokens.find_pair(2).ok_or(ParseError::new(
@@ -96,115 +239,356 @@ fn parse_function_declaration<'a>(tokens: &'a [Token]) -> Result<(Node<'a>, usiz
 
     let parameters = parse_parameters(&tokens[3..end_parenthesis])?;
 
-    if end_parenthesis == tokens.len() - 1 {
-        return Err(ParseError::new_unexpected_eof(tokens));
-    }
+    let (return_type, start_curly) = match tokens.get(end_parenthesis + 1) {
+        Some(Token {
+            data: TokenData::Arrow,
+            ..
+        }) => {
+            let type_start = end_parenthesis + 2;
+            let start_curly = tokens[type_start..]
+                .iter()
+                .position(|tk| tk.data == TokenData::OpenCurly)
+                .map(|i| type_start + i)
+                .ok_or(ParseError::new(
+                    ParseErrorData::ExpectedBlock,
+                    TextRange::from(tokens),
+                ))?;
+            if start_curly == type_start {
+                return Err(ParseError::new(
+                    ParseErrorData::InvalidType,
+                    tokens[end_parenthesis + 1].text_range.clone(),
+                ));
+            }
 
-    if !matches!(tokens[end_parenthesis + 1].data, TokenData::OpenCurly) {
-        return Err(ParseError::new_unexpected_token(
-            tokens[end_parenthesis + 1].clone(),
-        ));
-    }
+            let (return_type, used) = parse_type(&tokens[type_start..start_curly])?;
+            if type_start + used < start_curly {
+                return Err(ParseError::new_unexpected_token(
+                    tokens[type_start + used].clone(),
+                ));
+            }
 
-    let end_curly = tokens
-        .find_pair(end_parenthesis + 1)
-        .ok_or(ParseError::new(
-            ParseErrorData::UnclosedCurly,
-            TextRange::from(tokens),
-        ))?;
+            (Some(return_type), start_curly)
+        }
+        _ => (None, end_parenthesis + 1),
+    };
 
-    let body = parse_tks(&tokens[end_parenthesis + 2..end_curly])?;
+    let (body, end_curly) = parse_braced_block(tokens, start_curly, errors)?;
 
     let text_range = BorrowedTextRange::from(&tokens[0..=end_curly]);
-    let data = NodeData::FunctionDeclaration(identifier, parameters, body).to_box();
+    let data = NodeData::FunctionDeclaration(identifier, parameters, return_type, body, Vec::new())
+        .to_box();
 
     Ok((Node { data, text_range }, end_curly + 1))
 }
 
-fn parse_if<'a>(tokens: &'a [Token]) -> Result<(Node<'a>, usize), ParseError> {
-    assert!(matches!(tokens[0].data, TokenData::If));
+fn parse_if<'a>(
+    tokens: &'a [Token],
+    errors: &mut Vec<ParseError>,
+) -> Result<(Node<'a>, usize), ParseError> {
+    let (condition, body, end_curly) = parse_headed_block(tokens, errors)?;
+
+    let else_start = end_curly + 2;
+    let (else_node, used) = match (
+        tokens.get(end_curly + 1).map(|tk| &tk.data),
+        tokens.get(else_start).map(|tk| &tk.data),
+    ) {
+        (Some(TokenData::Else), Some(TokenData::If)) => {
+            let (else_if, else_if_used) = parse_if(&tokens[else_start..], errors)?;
+            (Some(else_if), else_start + else_if_used)
+        }
+        (Some(TokenData::Else), _) => {
+            let (else_body, else_end_curly) = parse_braced_block(tokens, else_start, errors)?;
+            let text_range = BorrowedTextRange::from(&tokens[end_curly + 1..=else_end_curly]);
+            let data = NodeData::Else(else_body).to_box();
+
+            (Some(Node { text_range, data }), else_end_curly + 1)
+        }
+        _ => (None, end_curly + 1),
+    };
+
+    let text_range = BorrowedTextRange::from(&tokens[0..used]);
+    let data = NodeData::If(condition, body, else_node).to_box();
+
+    Ok((Node { data, text_range }, used))
+}
+
+/// Parses `while`, `loop`, `repeat` and `do ... until` loops.
+fn parse_loop<'a>(
+    tokens: &'a [Token],
+    errors: &mut Vec<ParseError>,
+) -> Result<(Node<'a>, usize), ParseError> {
+    let (data, end) = match tokens[0].data {
+        TokenData::While => {
+            let (condition, body, end_curly) = parse_headed_block(tokens, errors)?;
+            (NodeData::While(condition, body), end_curly)
+        }
+        TokenData::Repeat => {
+            let (count, body, end_curly) = parse_headed_block(tokens, errors)?;
+            (NodeData::Repeat(count, body), end_curly)
+        }
+        TokenData::Loop => {
+            let (body, end_curly) = parse_braced_block(tokens, 1, errors)?;
+            (NodeData::Loop(body), end_curly)
+        }
+        _ => {
+            let (body, end_curly) = parse_braced_block(tokens, 1, errors)?;
+
+            let until = tokens.get(end_curly + 1);
+            if !matches!(until.map(|tk| &tk.data), Some(TokenData::Until)) {
+                let text_range = until.unwrap_or(&tokens[end_curly]).text_range.clone();
+                return Err(ParseError::new(ParseErrorData::ExpectedUntil, text_range));
+            }
+
+            let semilicon = end_curly + 2 + find_semilicon(&tokens[end_curly + 2..])?;
+            let condition =
+                parse_expr_after(&tokens[end_curly + 1], &tokens[end_curly + 2..semilicon])?;
+            (NodeData::DoUntil(body, condition), semilicon)
+        }
+    };
+
+    let text_range = BorrowedTextRange::from(&tokens[..=end]);
+
+    Ok((
+        Node {
+            data: data.to_box(),
+            text_range,
+        },
+        end + 1,
+    ))
+}
+
+fn parse_for<'a>(
+    tokens: &'a [Token],
+    errors: &mut Vec<ParseError>,
+) -> Result<(Node<'a>, usize), ParseError> {
+    let variable = parse_identifier(tokens, 1)?;
+
+    match tokens.get(2) {
+        Some(Token {
+            data: TokenData::In,
+            ..
+        }) => {}
+        tk => {
+            let text_range = tk.unwrap_or(&tokens[1]).text_range.clone();
+            return Err(ParseError::new(ParseErrorData::ExpectedIn, text_range));
+        }
+    }
 
     let start_curly = tokens
         .find_free(TokenData::OpenCurly)
         .ok_or(ParseError::new(
-            ParseErrorData::UnexpectedEOF,
+            ParseErrorData::ExpectedBlock,
             TextRange::from(tokens),
         ))?;
-    let end_curly = tokens.find_pair(start_curly).ok_or(ParseError::new(
-        ParseErrorData::UnclosedCurly,
-        TextRange::from(tokens),
-    ))?;
-    let condition = parse_expr(&tokens[1..start_curly])?;
-    let body = parse_tks(&tokens[start_curly + 1..end_curly])?;
+    if start_curly == 3 {
+        return Err(ParseError::new(
+            ParseErrorData::ExpectedExpression,
+            tokens[2].text_range.clone(),
+        ));
+    }
 
-    let (else_node, used) =
-        if let Some(TokenData::Else) = tokens.get(end_curly + 1).map(|tk| &tk.data) {
-            if end_curly + 1 == tokens.len() - 1 {
-                return Err(ParseError::new(
-                    ParseErrorData::UnexpectedEOF,
-                    TextRange::from(tokens),
-                ));
-            }
+    let iterable = parse_iterable(&tokens[3..start_curly])?;
+    let (body, end_curly) = parse_braced_block(tokens, start_curly, errors)?;
 
-            if !matches!(tokens[end_curly + 2].data, TokenData::OpenCurly) {
-                return Err(ParseError::new_unexpected_token(
-                    tokens[end_curly + 2].clone(),
-                ));
-            }
+    let text_range = BorrowedTextRange::from(&tokens[..=end_curly]);
+    let data = NodeData::For(variable, iterable, body).to_box();
 
-            let else_start_curly = end_curly + 2;
-            let else_end_curly = tokens.find_pair(else_start_curly).ok_or(ParseError::new(
-                ParseErrorData::UnclosedCurly,
-                TextRange::from(tokens),
-            ))?;
+    Ok((Node { data, text_range }, end_curly + 1))
+}
 
-            let else_body = parse_tks(&tokens[else_start_curly + 1..else_end_curly])?;
-            let text_range = BorrowedTextRange::from(&tokens[end_curly + 1..=else_end_curly]);
-            let data = NodeData::Else(else_body).to_box();
+/// Reports `break` and `continue` outside of a loop. Functions start outside of
+/// any loop, even when declared inside one.
+pub fn check_loop_control(node: &Node, in_loop: bool, errors: &mut Vec<ParseError>) {
+    match &*node.data {
+        NodeData::Program(statements) => {
+            for stmt in statements {
+                check_loop_control(stmt, in_loop, errors);
+            }
+        }
+        NodeData::FunctionDeclaration(_, _, _, body, _) => check_loop_control(body, false, errors),
+        NodeData::If(_, body, else_node) => {
+            check_loop_control(body, in_loop, errors);
+            if let Some(else_node) = else_node {
+                check_loop_control(else_node, in_loop, errors);
+            }
+        }
+        NodeData::Else(body) => check_loop_control(body, in_loop, errors),
+        NodeData::While(_, body)
+        | NodeData::Repeat(_, body)
+        | NodeData::Loop(body)
+        | NodeData::DoUntil(body, _)
+        | NodeData::For(_, _, body) => check_loop_control(body, true, errors),
+        NodeData::Break | NodeData::Continue if !in_loop => {
+            let data = match *node.data {
+                NodeData::Break => ParseErrorData::BreakOutsideLoop,
+                _ => ParseErrorData::ContinueOutsideLoop,
+            };
+            errors.push(ParseError::new(
+                data,
+                TextRange::from(node.text_range.clone()),
+            ));
+        }
+        _ => {}
+    }
+}
 
-            (Some(Node { text_range, data }), else_end_curly + 1)
-        } else {
-            (None, end_curly + 1)
-        };
+/// Expressions that can be assigned to.
+fn is_place_expr(node: &Node) -> bool {
+    matches!(
+        *node.data,
+        NodeData::Identifier(_)
+            | NodeData::StructAccess(..)
+            | NodeData::ModAccess(..)
+            | NodeData::ArrayAccess(..)
+    )
+}
 
-    let text_range = BorrowedTextRange::from(&tokens[0..]);
-    let data = NodeData::If(condition, body, else_node).to_box();
+fn check_assignment_target(target: &Node) -> Result<(), ParseError> {
+    let is_valid = match &*target.data {
+        NodeData::Tuple(targets) => targets.iter().all(is_place_expr),
+        _ => is_place_expr(target),
+    };
 
-    Ok((Node { data, text_range }, used))
-}
+    if !is_valid {
+        return Err(ParseError::new(
+            ParseErrorData::InvalidAssignmentTarget,
+            TextRange::from(target.text_range.clone()),
+        ));
+    }
 
-fn parse_variable_modification<'a>(tokens: &'a [Token]) -> Result<Node<'a>, ParseError> {
-    assert!(matches!(
-        tokens.last().expect("Tokens slice is empty").data,
-        TokenData::Semilicon
-    ));
+    Ok(())
+}
 
-    let mut variable_mod: Option<(usize, VariableModificationType)> = None;
+/// Parses `tokens`, which run up to and including the closing semilicon, as an
+/// assignment or as a call or access used as a statement.
+fn parse_expr_statement<'a>(tokens: &'a [Token]) -> Result<Node<'a>, ParseError> {
+    let semilicon = tokens.len() - 1;
+    let text_range = BorrowedTextRange::from(tokens);
 
-    let mut switch = false;
-    for (i, tk) in tokens.iter().enumerate() {
-        let variable_mod_type_maybe = tk.data.variable_modification_type();
-        if let Some(variable_mod_type) = variable_mod_type_maybe {
-            variable_mod = Some((i, variable_mod_type));
-            break;
+    let mut delimiter_checker = DelimiterChecker::new();
+    let assignment = tokens[..semilicon].iter().enumerate().find_map(|(i, tk)| {
+        let _ = delimiter_checker.check(tk);
+        let mod_type = tk.data.variable_modification_type()?;
+        delimiter_checker.is_free().then_some((i, mod_type))
+    });
+
+    let Some((mod_idx, variable_mod)) = assignment else {
+        let expr = parse_expr(&tokens[..semilicon])?;
+        if !matches!(*expr.data, NodeData::FunctionCall(..)) && !is_place_expr(&expr) {
+            return Err(ParseError::new(
+                ParseErrorData::NotAStatement,
+                TextRange::from(expr.text_range.clone()),
+            ));
         }
 
-        if (!switch && !matches!(tk.data, TokenData::Identifier(_)))
-            || (switch && !matches!(tk.data, TokenData::Comma))
-        {
-            return Err(ParseError::new_unexpected_token(tk.clone()));
-        }
-        switch = !switch;
+        let data = NodeData::ExprStatement(expr).to_box();
+        return Ok(Node { text_range, data });
+    };
+
+    let target = parse_expr(&tokens[..mod_idx])?;
+    check_assignment_target(&target)?;
+    let value = parse_expr_after(&tokens[mod_idx], &tokens[mod_idx + 1..semilicon])?;
+
+    let data = NodeData::VariableModification(target, variable_mod, value).to_box();
+
+    Ok(Node { text_range, data })
+}
+
+/// Whether running `node` always ends in a `return`.
+fn always_returns(node: &Node) -> bool {
+    match &*node.data {
+        // Statements after a `break` might never run.
+        NodeData::Program(statements) => statements
+            .iter()
+            .find(|stmt| always_returns(stmt) || breaks_out(stmt))
+            .is_some_and(always_returns),
+        NodeData::Return(_) => true,
+        NodeData::If(_, body, Some(else_node)) => always_returns(body) && always_returns(else_node),
+        NodeData::Else(body) | NodeData::DoUntil(body, _) => always_returns(body),
+        // Without a `break` the only way out of a `loop` is returning.
+        NodeData::Loop(body) => !breaks_out(body),
+        _ => false,
     }
+}
 
-    let (mod_idx, variable_mod) =
-        variable_mod.ok_or_else(|| ParseError::new_unexpected_eof(tokens))?;
+/// Whether `node` has a `break` for the loop it is in.
+fn breaks_out(node: &Node) -> bool {
+    match &*node.data {
+        NodeData::Program(statements) => statements.iter().any(breaks_out),
+        NodeData::Break => true,
+        NodeData::If(_, body, else_node) => {
+            breaks_out(body) || else_node.as_ref().is_some_and(breaks_out)
+        }
+        NodeData::Else(body) => breaks_out(body),
+        _ => false,
+    }
+}
 
-    let id = parse_expr(&tokens[0..mod_idx])?;
-    let value = parse_expr(&tokens[mod_idx + 1..tokens.len() - 1])?;
+/// Reports functions with a return type that can finish without returning a value.
+pub fn check_returns(node: &Node, errors: &mut Vec<ParseError>) {
+    match &*node.data {
+        NodeData::Program(statements) => {
+            for stmt in statements {
+                check_returns(stmt, errors);
+            }
+        }
+        NodeData::FunctionDeclaration(name, _, return_type, body, _) => {
+            // `-> Void` is the same as leaving the return type out.
+            let returns = return_type.as_ref().is_some_and(
+                |r#type| !matches!(&*r#type.data, NodeData::Type(r#type) if r#type.is_void()),
+            );
+            if returns {
+                if !always_returns(body) {
+                    errors.push(ParseError::new(
+                        ParseErrorData::MissingReturn,
+                        TextRange::from(name.text_range.clone()),
+                    ));
+                }
+                check_return_values(body, errors);
+            }
 
-    let text_range = BorrowedTextRange::from(tokens);
-    let data = NodeData::VariableModification(id, variable_mod, value).to_box();
+            check_returns(body, errors);
+        }
+        NodeData::If(_, body, else_node) => {
+            check_returns(body, errors);
+            if let Some(else_node) = else_node {
+                check_returns(else_node, errors);
+            }
+        }
+        NodeData::Else(body)
+        | NodeData::While(_, body)
+        | NodeData::Repeat(_, body)
+        | NodeData::Loop(body)
+        | NodeData::DoUntil(body, _)
+        | NodeData::For(_, _, body) => check_returns(body, errors),
+        _ => {}
+    }
+}
 
-    Ok(Node { text_range, data })
+/// Reports `return;` in the body of a function that has a return type.
+fn check_return_values(node: &Node, errors: &mut Vec<ParseError>) {
+    match &*node.data {
+        NodeData::Program(statements) => {
+            for stmt in statements {
+                check_return_values(stmt, errors);
+            }
+        }
+        NodeData::Return(None) => errors.push(ParseError::new(
+            ParseErrorData::MissingReturnValue,
+            TextRange::from(node.text_range.clone()),
+        )),
+        NodeData::If(_, body, else_node) => {
+            check_return_values(body, errors);
+            if let Some(else_node) = else_node {
+                check_return_values(else_node, errors);
+            }
+        }
+        NodeData::Else(body)
+        | NodeData::While(_, body)
+        | NodeData::Repeat(_, body)
+        | NodeData::Loop(body)
+        | NodeData::DoUntil(body, _)
+        | NodeData::For(_, _, body) => check_return_values(body, errors),
+        _ => {}
+    }
 }
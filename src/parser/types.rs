@@ -1,17 +1,38 @@
+use std::fmt;
+
 use crate::{
     text_data::BorrowedTextRange,
     tokenizer::{Token, TokenData},
 };
 
+#[derive(Debug, Clone)]
 pub enum YoyogayType<'a> {
     Identifier(&'a String, Vec<YoyogayType<'a>>),
     Array(Box<YoyogayType<'a>>),
     Tuple(Vec<YoyogayType<'a>>),
 }
 
+impl fmt::Display for YoyogayType<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let join = |types: &[YoyogayType]| {
+            let types: Vec<String> = types.iter().map(|t| t.to_string()).collect();
+            types.join(", ")
+        };
+
+        let txt = match self {
+            YoyogayType::Identifier(id, generics) if generics.is_empty() => id.to_string(),
+            YoyogayType::Identifier(id, generics) => format!("{}<{}>", id, join(generics)),
+            YoyogayType::Array(inner) => format!("[{}]", inner),
+            YoyogayType::Tuple(types) => format!("({})", join(types)),
+        };
+
+        f.write_str(&txt)
+    }
+}
+
 pub struct TypeParsingError<'a> {
-    data: TypeParsingErrorData<'a>,
-    text_data: BorrowedTextRange<'a>,
+    pub data: TypeParsingErrorData<'a>,
+    pub text_data: BorrowedTextRange<'a>,
 }
 
 impl TypeParsingError<'_> {
@@ -26,35 +47,193 @@ impl TypeParsingError<'_> {
 pub enum TypeParsingErrorData<'a> {
     UnexpectedToken(&'a Token),
     UnexpectedTokens(&'a [Token]),
-    EmptyTokenStreamAfterBrackets,
+    EmptyTokenStream,
+    UnclosedGenerics,
 }
 
 impl YoyogayType<'_> {
+    /// Whether it is `Void`, the return type of functions that return nothing.
+    pub fn is_void(&self) -> bool {
+        matches!(self, YoyogayType::Identifier(id, generics) if id.as_str() == "Void" && generics.is_empty())
+    }
+
+    /// Parses the whole slice as one type.
     pub fn create_from_tokens<'a>(
         tokens: &'a [Token],
     ) -> Result<YoyogayType<'a>, TypeParsingError<'a>> {
-        assert_ne!(tokens.len(), 0);
-
-        match (
-            tokens.get(0).map(|tk| &tk.data),
-            tokens.get(1).map(|tk| &tk.data),
-        ) {
-            (Some(TokenData::OpenBracket), Some(TokenData::CloseBracket)) => {
-                if tokens.len() < 3 {
-                    Err(TypeParsingError::new(
-                        TypeParsingErrorData::EmptyTokenStreamAfterBrackets,
-                        BorrowedTextRange::from((&tokens[0], &tokens[1])),
-                    ))
-                } else {
-                    YoyogayType::create_from_tokens(&tokens[2..])
+        let (parsed, used) = YoyogayType::create_from_token_prefix(tokens)?;
+
+        if used < tokens.len() {
+            return Err(TypeParsingError::new(
+                TypeParsingErrorData::UnexpectedTokens(&tokens[used..]),
+                BorrowedTextRange::from(&tokens[used..]),
+            ));
+        }
+
+        Ok(parsed)
+    }
+
+    /// Parses the type at the start of `tokens`, returning it and how many tokens it used.
+    pub fn create_from_token_prefix<'a>(
+        tokens: &'a [Token],
+    ) -> Result<(YoyogayType<'a>, usize), TypeParsingError<'a>> {
+        let mut parser = TypeParser {
+            tokens,
+            ptr: 0,
+            split_shift: false,
+        };
+        let parsed = parser.parse_type()?;
+
+        if parser.split_shift {
+            return Err(TypeParsingError::new(
+                TypeParsingErrorData::UnexpectedToken(&tokens[parser.ptr - 1]),
+                BorrowedTextRange::from(&tokens[parser.ptr - 1]),
+            ));
+        }
+
+        Ok((parsed, parser.ptr))
+    }
+}
+
+struct TypeParser<'a> {
+    tokens: &'a [Token],
+    ptr: usize,
+    /// Set when only the first `>` of a `>>` token closed a generic list.
+    split_shift: bool,
+}
+
+impl<'a> TypeParser<'a> {
+    fn peek(&self) -> Option<&'a TokenData> {
+        self.tokens.get(self.ptr).map(|tk| &tk.data)
+    }
+
+    /// Error for the current token, or for running out of tokens.
+    fn unexpected(&self) -> TypeParsingError<'a> {
+        // Half of a `>>` is left over, so the token is unexpected after all.
+        if self.split_shift {
+            let tk = &self.tokens[self.ptr - 1];
+            return TypeParsingError::new(
+                TypeParsingErrorData::UnexpectedToken(tk),
+                BorrowedTextRange::from(tk),
+            );
+        }
+
+        match self.tokens.get(self.ptr) {
+            Some(tk) => TypeParsingError::new(
+                TypeParsingErrorData::UnexpectedToken(tk),
+                BorrowedTextRange::from(tk),
+            ),
+            None => TypeParsingError::new(
+                TypeParsingErrorData::EmptyTokenStream,
+                BorrowedTextRange::from(&self.tokens[self.tokens.len().saturating_sub(1)..]),
+            ),
+        }
+    }
+
+    fn expect(&mut self, expected: TokenData) -> Result<(), TypeParsingError<'a>> {
+        if self.split_shift || self.peek() != Some(&expected) {
+            return Err(self.unexpected());
+        }
+        self.ptr += 1;
+
+        Ok(())
+    }
+
+    /// Comma separated types up to `close`, a trailing comma is allowed.
+    fn parse_list(
+        &mut self,
+        close: TokenData,
+    ) -> Result<(Vec<YoyogayType<'a>>, bool), TypeParsingError<'a>> {
+        let mut types = Vec::new();
+        let mut has_comma = false;
+
+        while self.peek() != Some(&close) {
+            types.push(self.parse_type()?);
+
+            match self.peek() {
+                Some(TokenData::Comma) if !self.split_shift => {
+                    self.ptr += 1;
+                    has_comma = true;
                 }
+                _ => break,
             }
+        }
+        self.expect(close)?;
 
-            (Some(TokenData::Identifier(ref id)), Some(TokenData::GreaterThan)) => Ok(
-                YoyogayType::Identifier(id, vec![YoyogayType::create_from_tokens(&tokens[2..])?]),
-            ),
+        Ok((types, has_comma))
+    }
+
+    fn parse_type(&mut self) -> Result<YoyogayType<'a>, TypeParsingError<'a>> {
+        let Some(tk) = self.tokens.get(self.ptr) else {
+            return Err(self.unexpected());
+        };
+        self.ptr += 1;
+
+        match &tk.data {
+            TokenData::OpenBracket => {
+                let inner = self.parse_type()?;
+                self.expect(TokenData::CloseBracket)?;
+
+                Ok(YoyogayType::Array(Box::new(inner)))
+            }
+            TokenData::OpenParenthesis => {
+                let (mut types, has_comma) = self.parse_list(TokenData::CloseParenthesis)?;
+
+                // `(Real)` is only grouping, one element tuples need a comma.
+                if types.len() == 1 && !has_comma {
+                    return Ok(types.remove(0));
+                }
 
-            _ => panic!(),
+                Ok(YoyogayType::Tuple(types))
+            }
+            TokenData::Identifier(id) => {
+                if self.peek() != Some(&TokenData::LessThan) {
+                    return Ok(YoyogayType::Identifier(id, Vec::new()));
+                }
+                self.ptr += 1;
+
+                let generics = self.parse_generics()?;
+                Ok(YoyogayType::Identifier(id, generics))
+            }
+            _ => Err(TypeParsingError::new(
+                TypeParsingErrorData::UnexpectedToken(tk),
+                BorrowedTextRange::from(tk),
+            )),
+        }
+    }
+
+    /// Generic arguments after the `<`, including the closing `>`. A `>>` closes
+    /// two lists, the first half is taken here and the second by the outer list.
+    fn parse_generics(&mut self) -> Result<Vec<YoyogayType<'a>>, TypeParsingError<'a>> {
+        let mut generics = Vec::new();
+
+        loop {
+            generics.push(self.parse_type()?);
+
+            if self.split_shift {
+                self.split_shift = false;
+                return Ok(generics);
+            }
+
+            match self.peek() {
+                Some(TokenData::Comma) => self.ptr += 1,
+                Some(TokenData::GreaterThan) => {
+                    self.ptr += 1;
+                    return Ok(generics);
+                }
+                Some(TokenData::ShiftRight) => {
+                    self.ptr += 1;
+                    self.split_shift = true;
+                    return Ok(generics);
+                }
+                Some(_) => return Err(self.unexpected()),
+                None => {
+                    return Err(TypeParsingError::new(
+                        TypeParsingErrorData::UnclosedGenerics,
+                        BorrowedTextRange::from(self.tokens),
+                    ))
+                }
+            }
         }
     }
 }
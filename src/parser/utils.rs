@@ -1,30 +1,94 @@
 use crate::{
     ast::{Node, NodeData},
-    text_data::BorrowedTextRange,
-    tokenizer::{Token, TokenData, TokensUtils},
+    text_data::{BorrowedTextRange, TextRange},
+    tokenizer::{Token, TokenData},
     Boxxable,
 };
 
-use super::ParseError;
+use super::{types::YoyogayType, ParseError, ParseErrorData};
 
 pub mod delimiter_checker;
 
-pub fn parse_parameters<'a>(tokens: &'a [Token]) -> Result<Vec<Node<'a>>, ParseError> {
-    tokens.split_tks(TokenData::Comma).into_iter().map(|tks| {
-        assert_eq!(tks.len(), 1);
-        let identifier = (if let TokenData::Identifier(ref id) = tks[0].data {
-            let text_range = BorrowedTextRange::from(&tks[0]);
-            Ok(Node {data: NodeData::Identifier(id).to_box(), text_range})
-        } else {
-            Err(ParseError::new_unexpected_token(tks[0].clone()))
-        })?;
-
-        let text_range = BorrowedTextRange::from(tks);
-        let data = NodeData::FunctionParemeter(identifier).to_box();
-        
-        Ok(Node {
-            data,
+/// Identifier node for `tokens[idx]`. When the slice ends early the error
+/// points at its last token.
+pub fn parse_identifier<'a>(tokens: &'a [Token], idx: usize) -> Result<Node<'a>, ParseError> {
+    match tokens.get(idx) {
+        Some(Token {
+            data: TokenData::Identifier(id),
             text_range,
-        })
-    }).collect()
+        }) => Ok(Node {
+            data: NodeData::Identifier(id).to_box(),
+            text_range: BorrowedTextRange::from(text_range),
+        }),
+        Some(tk) => Err(ParseError::new(
+            ParseErrorData::ExpectedIdentifier,
+            tk.text_range.clone(),
+        )),
+        None => Err(ParseError::new(
+            ParseErrorData::ExpectedIdentifier,
+            tokens
+                .last()
+                .map_or_else(TextRange::new_empty, |tk| tk.text_range.clone()),
+        )),
+    }
+}
+
+/// `Type` node for the type at the start of `tokens`, along with how many tokens it used.
+pub fn parse_type<'a>(tokens: &'a [Token]) -> Result<(Node<'a>, usize), ParseError> {
+    let (r#type, used) = YoyogayType::create_from_token_prefix(tokens)?;
+    let text_range = BorrowedTextRange::from(&tokens[..used]);
+    let data = NodeData::Type(r#type).to_box();
+
+    Ok((Node { data, text_range }, used))
+}
+
+/// Type annotation after the `:` at `tokens[colon]`, if there is one. Returns the
+/// `Type` node and the index right after it.
+pub fn parse_type_annotation<'a>(
+    tokens: &'a [Token],
+    colon: usize,
+) -> Result<(Option<Node<'a>>, usize), ParseError> {
+    match tokens.get(colon) {
+        Some(Token {
+            data: TokenData::Colon,
+            ..
+        }) => {
+            if colon + 1 == tokens.len() {
+                return Err(ParseError::new(
+                    ParseErrorData::InvalidType,
+                    tokens[colon].text_range.clone(),
+                ));
+            }
+
+            let (r#type, used) = parse_type(&tokens[colon + 1..])?;
+            Ok((Some(r#type), colon + 1 + used))
+        }
+        _ => Ok((None, colon)),
+    }
+}
+
+/// Comma separated identifiers with optional types, a trailing comma is allowed.
+pub fn parse_parameters<'a>(tokens: &'a [Token]) -> Result<Vec<Node<'a>>, ParseError> {
+    let mut parameters = Vec::new();
+    let mut ptr = 0;
+
+    while ptr < tokens.len() {
+        let identifier = parse_identifier(tokens, ptr)?;
+        let (r#type, end) = parse_type_annotation(tokens, ptr + 1)?;
+
+        let text_range = BorrowedTextRange::from(&tokens[ptr..end]);
+        let data = NodeData::FunctionParemeter(identifier, r#type).to_box();
+        parameters.push(Node { data, text_range });
+
+        match tokens.get(end) {
+            Some(Token {
+                data: TokenData::Comma,
+                ..
+            })
+            | None => ptr = end + 1,
+            Some(tk) => return Err(ParseError::new_unexpected_token(tk.clone())),
+        }
+    }
+
+    Ok(parameters)
 }
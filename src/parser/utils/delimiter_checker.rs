@@ -12,6 +12,12 @@ pub enum DelimiterCheckerError<'a> {
     UnexpectedClosingBracket(&'a Token),
 }
 
+impl Default for DelimiterChecker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl DelimiterChecker {
     pub fn new() -> Self {
         DelimiterChecker {
@@ -35,21 +41,21 @@ impl DelimiterChecker {
                 if self.paren_level > 0 {
                     self.paren_level -= 1;
                 } else {
-                    return Err(DelimiterCheckerError::UnexpectedClosingParenthesis(&token));
+                    return Err(DelimiterCheckerError::UnexpectedClosingParenthesis(token));
                 }
             }
             TokenData::CloseBracket => {
                 if self.brack_level > 0 {
                     self.brack_level -= 1;
                 } else {
-                    return Err(DelimiterCheckerError::UnexpectedClosingBracket(&token));
+                    return Err(DelimiterCheckerError::UnexpectedClosingBracket(token));
                 }
             }
             TokenData::CloseCurly => {
                 if self.curly_level > 0 {
                     self.curly_level -= 1;
                 } else {
-                    return Err(DelimiterCheckerError::UnexpectedClosingCurlyBrace(&token));
+                    return Err(DelimiterCheckerError::UnexpectedClosingCurlyBrace(token));
                 }
             }
 
@@ -69,21 +75,21 @@ impl DelimiterChecker {
                 if self.paren_level > 0 {
                     self.paren_level -= 1;
                 } else {
-                    return Err(DelimiterCheckerError::UnexpectedClosingParenthesis(&token));
+                    return Err(DelimiterCheckerError::UnexpectedClosingParenthesis(token));
                 }
             }
             TokenData::OpenBracket => {
                 if self.brack_level > 0 {
                     self.brack_level -= 1;
                 } else {
-                    return Err(DelimiterCheckerError::UnexpectedClosingBracket(&token));
+                    return Err(DelimiterCheckerError::UnexpectedClosingBracket(token));
                 }
             }
             TokenData::OpenCurly => {
                 if self.curly_level > 0 {
                     self.curly_level -= 1;
                 } else {
-                    return Err(DelimiterCheckerError::UnexpectedClosingCurlyBrace(&token));
+                    return Err(DelimiterCheckerError::UnexpectedClosingCurlyBrace(token));
                 }
             }
 
@@ -1 +1,8 @@
+pub mod codegen;
+pub mod fuzz;
+pub mod organizer;
+pub mod parser;
+pub mod resolver;
+pub mod resource;
 pub mod tokenizer;
+pub mod type_check;
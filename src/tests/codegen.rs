@@ -0,0 +1,216 @@
+use crate::{gamemaker::codegen::generate_gml, parser::parse_tks, tokenizer::tokenize};
+
+#[test]
+fn declarations_and_precedence() {
+    let tokens = tokenize("let spd = 2 * (3 + 4);\nx = -y - (1 - 2);").unwrap();
+    let (program, errors) = parse_tks(&tokens);
+    assert!(errors.is_empty());
+
+    assert_eq!(
+        generate_gml(&program),
+        "var spd = 2 * (3 + 4);\nx = -y - (1 - 2);\n"
+    );
+}
+
+#[test]
+fn declaration_kinds() {
+    let tokens = tokenize("let a = 1;\nconst B = 2;\nvar hp = a + B;").unwrap();
+    let (program, errors) = parse_tks(&tokens);
+    assert!(errors.is_empty());
+
+    assert_eq!(generate_gml(&program), "var a = 1;\nvar B = 2;\nhp = a + B;\n");
+}
+
+#[test]
+fn blocks_are_indented() {
+    let tokens = tokenize("fn hurt(amount) {\nif hp == 0 {\nhp = 10;\n} else {\nhp -= amount;\n}\n}").unwrap();
+    let (program, errors) = parse_tks(&tokens);
+    assert!(errors.is_empty());
+
+    assert_eq!(
+        generate_gml(&program),
+        "function hurt(amount) {\n    if (hp == 0) {\n        hp = 10;\n    } else {\n        hp -= amount;\n    }\n}\n"
+    );
+}
+
+#[test]
+fn tuple_assignment_goes_through_a_temporary() {
+    let tokens = tokenize("a, b = b, a;").unwrap();
+    let (program, errors) = parse_tks(&tokens);
+    assert!(errors.is_empty());
+
+    assert_eq!(
+        generate_gml(&program),
+        "var __yyg_tuple_0 = [b, a];\na = __yyg_tuple_0[0];\nb = __yyg_tuple_0[1];\n"
+    );
+}
+
+#[test]
+fn strings_are_escaped_for_gml() {
+    let tokens = tokenize("msg = concat(\"say \\\"hi\\\"\nnow\\\\\");").unwrap();
+    let (program, errors) = parse_tks(&tokens);
+    assert!(errors.is_empty());
+
+    assert_eq!(
+        generate_gml(&program),
+        "msg = concat(\"say \\\"hi\\\"\\nnow\\\\\");\n"
+    );
+}
+
+#[test]
+fn numeric_literals_keep_their_spelling() {
+    let tokens = tokenize("c = $FF8000 + 0xFF * 1_000 - 0.5e2;").unwrap();
+    let (program, errors) = parse_tks(&tokens);
+    assert!(errors.is_empty());
+
+    assert_eq!(generate_gml(&program), "c = $FF8000 + 0xFF * 1000 - 0.5e2;\n");
+}
+
+#[test]
+fn doc_comments_become_feather_headers() {
+    let src = "/// Deals damage to the player.\n/// @param {Real} amount How much to take\n/// @return {Bool}\nfn hurt(amount, source) {\nhp -= amount;\n}\n/// Current health\nvar hp = 10;";
+    let tokens = tokenize(src).unwrap();
+    let (program, errors) = parse_tks(&tokens);
+    assert!(errors.is_empty());
+
+    assert_eq!(
+        generate_gml(&program),
+        "/// @description Deals damage to the player.\n/// @param {Real} amount How much to take\n/// @param source\n/// @return {Bool}\nfunction hurt(amount, source) {\n    hp -= amount;\n}\n/// @description Current health\nhp = 10;\n"
+    );
+}
+
+#[test]
+fn comparison_logical_and_bitwise_operators() {
+    let src = "if hp <= 0 && !alive || flags & 4 == 0 {\nx = a div 2 % 3 + (b << 1 | ~c ^ d) - e ^^ f;\n}";
+    let tokens = tokenize(src).unwrap();
+    let (program, errors) = parse_tks(&tokens);
+    assert!(errors.is_empty());
+
+    assert_eq!(
+        generate_gml(&program),
+        "if (((hp <= 0) && !alive) || ((flags & 4) == 0)) {\n    x = a div 2 % 3 + (b << 1 | (~c ^ d)) - e ^^ f;\n}\n"
+    );
+}
+
+#[test]
+fn expression_statements() {
+    let tokens = tokenize("instance_destroy();\nother.speed *= 2;").unwrap();
+    let (program, errors) = parse_tks(&tokens);
+    assert!(errors.is_empty());
+
+    assert_eq!(generate_gml(&program), "instance_destroy();\nother.speed *= 2;\n");
+}
+
+#[test]
+fn loops() {
+    let src = "while hp > 0 {\n    hp -= 1;\n}\nloop {\n    if done {\n        break;\n    }\n}\nrepeat 3 {\n    continue;\n}\ndo {\n    x += 1;\n} until x >= 10;";
+    let tokens = tokenize(src).unwrap();
+    let (program, errors) = parse_tks(&tokens);
+    assert!(errors.is_empty(), "{:?}", errors);
+
+    assert_eq!(
+        generate_gml(&program),
+        "while (hp > 0) {\n    hp -= 1;\n}\nwhile (true) {\n    if (done) {\n        break;\n    }\n}\nrepeat (3) {\n    continue;\n}\ndo {\n    x += 1;\n} until (x >= 10);\n"
+    );
+}
+
+#[test]
+fn for_loops() {
+    let src = "for i in 0..n {\n    f(i);\n}\nfor i in (0..=10).step(2) {\n    f(i);\n}\nfor i in (len(a)..0).step(-1) {\n    f(i);\n}\nfor item in get_items() {\n    f(item);\n}";
+    let tokens = tokenize(src).unwrap();
+    let (program, errors) = parse_tks(&tokens);
+    assert!(errors.is_empty(), "{:?}", errors);
+
+    assert_eq!(
+        generate_gml(&program),
+        "var __yyg_end_0 = n;\nfor (var i = 0; i < __yyg_end_0; i++) {\n    f(i);\n}\n\
+         for (var i = 0; i <= 10; i += 2) {\n    f(i);\n}\n\
+         for (var i = len(a); i > 0; i += -1) {\n    f(i);\n}\n\
+         var __yyg_array_1 = get_items();\nvar __yyg_length_2 = array_length(__yyg_array_1);\n\
+         for (var __yyg_i_3 = 0; __yyg_i_3 < __yyg_length_2; __yyg_i_3++) {\n    var item = __yyg_array_1[__yyg_i_3];\n    f(item);\n}\n"
+    );
+}
+
+#[test]
+fn returns() {
+    let tokens = tokenize("fn twice(x) -> Real {\n    return x * 2;\n}\nfn stop() {\n    return;\n}").unwrap();
+    let (program, errors) = parse_tks(&tokens);
+    assert!(errors.is_empty());
+
+    assert_eq!(
+        generate_gml(&program),
+        "function twice(x) {\n    return x * 2;\n}\nfunction stop() {\n    return;\n}\n"
+    );
+}
+
+#[test]
+fn else_if_chains_stay_flat() {
+    let src = "if a {\n    x = 1;\n} else if b {\n    x = 2;\n} else if c {\n    x = 3;\n} else {\n    x = 4;\n}";
+    let tokens = tokenize(src).unwrap();
+    let (program, errors) = parse_tks(&tokens);
+    assert!(errors.is_empty(), "{:?}", errors);
+
+    assert_eq!(
+        generate_gml(&program),
+        "if (a) {\n    x = 1;\n} else if (b) {\n    x = 2;\n} else if (c) {\n    x = 3;\n} else {\n    x = 4;\n}\n"
+    );
+}
+
+#[test]
+fn annotated_types_fill_feather_tags() {
+    let src = "/// Distance between two points\nfn dist(a: (Real, Real), b: [Real], c) -> Real {\n    return 0;\n}";
+    let tokens = tokenize(src).unwrap();
+    let (program, errors) = parse_tks(&tokens);
+    assert!(errors.is_empty(), "{:?}", errors);
+
+    assert_eq!(
+        generate_gml(&program),
+        "/// @description Distance between two points\n/// @param {Array} a\n/// @param {Array<Real>} b\n/// @param c\n/// @return {Real}\nfunction dist(a, b, c) {\n    return 0;\n}\n"
+    );
+}
+
+#[test]
+fn super_calls_the_parent_event() {
+    let tokens = tokenize("super();\nif hp == 0 {\nsuper();\n}").unwrap();
+    let (program, errors) = parse_tks(&tokens);
+    assert!(errors.is_empty());
+
+    assert_eq!(
+        generate_gml(&program),
+        "event_inherited();\nif (hp == 0) {\n    event_inherited();\n}\n"
+    );
+
+    for src in ["super(1);", "super;", "super();;", "super()"] {
+        let tokens = tokenize(src).unwrap();
+        assert!(!parse_tks(&tokens).1.is_empty(), "{} should not parse", src);
+    }
+}
+
+#[test]
+fn malformed_ranges_are_not_compiled() {
+    for src in [
+        "for i in 0..n..m {}",
+        "for x in (0..1, 2) {}",
+        "for x in (0..1).step(2).step(3) {}",
+        "for x in (0..10).step(0) {}",
+        "for x in (0..10).step(-0.0) {}",
+    ] {
+        let tokens = tokenize(src).unwrap();
+        let (program, errors) = parse_tks(&tokens);
+        assert!(!errors.is_empty(), "{} should not parse", src);
+        generate_gml(&program);
+    }
+}
+
+#[test]
+fn runtime_steps_check_their_sign() {
+    let tokens = tokenize("for i in (a..=b).step(k) {\n    f(i);\n}").unwrap();
+    let (program, errors) = parse_tks(&tokens);
+    assert!(errors.is_empty(), "{:?}", errors);
+
+    assert_eq!(
+        generate_gml(&program),
+        "var __yyg_end_0 = b;\nvar __yyg_step_1 = k;\n\
+         for (var i = a; (__yyg_step_1 > 0 ? i <= __yyg_end_0 : i >= __yyg_end_0); i += __yyg_step_1) {\n    f(i);\n}\n"
+    );
+}
@@ -0,0 +1,151 @@
+use crate::{
+    gamemaker::codegen::generate_gml,
+    parser::{parse_tks, ParseErrorData},
+    text_data::TextPos,
+    tokenizer::tokenize,
+};
+
+/// Tokenizes and parses `src`, generating GML when it parses. Only panics
+/// are failures here, errors are fine.
+fn compile(src: &str) -> Option<ParseErrorData> {
+    let Ok(tokens) = tokenize(src) else {
+        return None;
+    };
+
+    let (program, errors) = parse_tks(&tokens);
+    generate_gml(&program);
+
+    errors.into_iter().next().map(|err| err.data)
+}
+
+const VALID_PROGRAMS: &[&str] = &[
+    "const speed = 4;\nvar hp = max_hp * 0.5;",
+    "fn heal(amount, limit) {\n    hp = min(hp + amount, limit);\n}",
+    "if hp <= 0 && !dead {\n    dead = true;\n} else {\n    hp -= 1;\n}",
+    "a, b = b, a;\nx = list[i + 1].y + obj_player::speed;",
+    "/// Doc\nfn f() {\n    x = (1, 2);\n}",
+    "while a {\n    repeat 2 {\n        continue;\n    }\n    loop {\n        break;\n    }\n}",
+    "do {\n    f();\n} until x == 3;",
+    "var grid: Array<Array<Real>> = f();\nfn g(a: Real, b: (Real, String)) -> [Real] {\n    return a, b;\n}",
+    "for i in (0..=n).step(2) {\n    for item in list {\n        f(item, i);\n    }\n}",
+    "for i in (a + 1..b).step(k) {\n    f((i, 2));\n}",
+];
+
+const PIECES: &[&str] = &[
+    "fn", "if", "else", "while", "loop", "repeat", "do", "until", "break", "continue", "for", "in", "..", "..=", "return", "->", "Real", ":", "<", ">", ">>", "const", "var", "let", "x", "f", "1", "0.5", "\"s\"", "=", "+=", "+",
+    "-", "*", "/", "!", "~", "&&", "==", "<", ">>", "div", ",", ".", "::", ";", "(", ")", "{",
+    "}", "[", "]", "/// doc\n", "(0..1, 2)", "0..a..b", ".step(1).step(2)",
+];
+
+/// Pieces of what a `for` iterates over, joined in pairs by `for_headers_never_panic`.
+const ITERABLE_PIECES: &[&str] = &[
+    "0..n", "0..=n", "..", "(0..1)", "(0..1, 2)", "(2, 0..1)", "0..a..b", ".step(1)",
+    ".step(1).step(2)", ".step(0)", ".step(k)", "+ 2", "-", "[0]", "(", ")", ",", "f(0..1)",
+];
+
+/// Deterministic xorshift so the corpus is the same on every run.
+struct XorShift(u64);
+
+impl XorShift {
+    fn next(&mut self) -> usize {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0 as usize
+    }
+}
+
+#[test]
+fn truncated_programs_never_panic() {
+    for program in VALID_PROGRAMS {
+        assert!(compile(program).is_none(), "{:?} should compile", program);
+
+        for (end, _) in program.char_indices() {
+            compile(&program[..end]);
+            compile(&program[end..]);
+        }
+    }
+}
+
+#[test]
+fn token_soup_never_panics() {
+    let mut rng = XorShift(0x9E37_79B9_7F4A_7C15);
+
+    for _ in 0..5000 {
+        let len = rng.next() % 16;
+        let src: Vec<&str> = (0..len).map(|_| PIECES[rng.next() % PIECES.len()]).collect();
+        compile(&src.join(" "));
+    }
+}
+
+#[test]
+fn for_headers_never_panic() {
+    for first in ITERABLE_PIECES {
+        for second in ITERABLE_PIECES {
+            compile(&format!("for i in {}{} {{\n    f(i);\n}}", first, second));
+            compile(&format!("for i in ({}){} {{}}", first, second));
+        }
+    }
+}
+
+#[test]
+fn malformed_statements_are_reported() {
+    let cases: &[(&str, &str)] = &[
+        ("const", "MissingSemilicon"),
+        ("const = 1;", "ExpectedIdentifier"),
+        ("var x = ;", "ExpectedExpression"),
+        ("x += ;", "ExpectedExpression"),
+        ("x = a.;", "ExpectedIdentifier"),
+        ("x = a + ;", "ExpectedExpression"),
+        ("fn", "ExpectedIdentifier"),
+        ("fn f(a b) {}", "UnexpectedToken"),
+        ("fn f()", "ExpectedBlock"),
+        ("if {}", "ExpectedExpression"),
+        ("if x {} else", "ExpectedBlock"),
+        ("1 + 2;", "UnexpectedStatementStart"),
+        ("a + b;", "NotAStatement"),
+        ("f() = 1;", "InvalidAssignmentTarget"),
+        ("a, b + 1 = 1, 2;", "InvalidAssignmentTarget"),
+        ("while {}", "ExpectedExpression"),
+        ("loop", "ExpectedBlock"),
+        ("do {} x;", "ExpectedUntil"),
+        ("do {} until;", "ExpectedExpression"),
+        ("loop { break }", "MissingSemilicon"),
+        ("for {}", "ExpectedIdentifier"),
+        ("for i {}", "ExpectedIn"),
+        ("for i in {}", "ExpectedExpression"),
+        ("for i in (0..n).step(0) {}", "ZeroStep"),
+        ("fn f() -> {}", "InvalidType"),
+        ("fn f() -> Real", "ExpectedBlock"),
+        ("return", "MissingSemilicon"),
+        ("var x: = 1;", "InvalidType"),
+        ("var x: Real;", "UnexpectedToken"),
+        ("fn f(a: Array<Real) {}", "InvalidType"),
+        ("fn f() -> Real Real {}", "UnexpectedToken"),
+    ];
+
+    for (src, expected) in cases {
+        match compile(src) {
+            Some(err) => {
+                let name = format!("{:?}", err);
+                assert!(name.starts_with(expected), "{:?} gave {}", src, name);
+            }
+            None => panic!("{:?} should not parse", src),
+        }
+    }
+}
+
+#[test]
+fn errors_point_at_the_problem() {
+    let tokens = tokenize("var hp = 1;\nvar x = ;").unwrap();
+    let err = parse_tks(&tokens).1.remove(0);
+
+    assert!(matches!(err.data, ParseErrorData::ExpectedExpression));
+    assert_eq!(err.text_range.start, TextPos { line: 2, pos: 7 });
+}
+
+#[test]
+fn empty_program() {
+    assert!(compile("").is_none());
+    assert!(compile("/// only a comment").is_none());
+}
@@ -0,0 +1,697 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::gamemaker::GameMakerProject;
+
+use crate::{
+    checker::{resolver::ResolveErrorData, type_check::TypeErrorData},
+    organizer::{
+        info::{Member, Symbol},
+        object::{ObjectParseError, ObjectWarning},
+        script::ScriptParseError,
+        Lints, OrganizeError, Warning, YoyogayProject,
+    },
+};
+
+/// What reading and checking the project at `root` fails with.
+fn check_error(root: &Path) -> OrganizeError {
+    YoyogayProject::create_from_directory(root)
+        .and_then(|project| project.check(Lints::default()).map(|_| ()))
+        .unwrap_err()
+}
+
+/// Fresh project directory in the temp dir with the given object files.
+fn project_with_objects(name: &str, objects: &[(&str, &str)]) -> PathBuf {
+    project_with_files(name, objects, &[])
+}
+
+/// Fresh project directory in the temp dir with the given object and script files.
+fn project_with_files(name: &str, objects: &[(&str, &str)], scripts: &[(&str, &str)]) -> PathBuf {
+    let root = std::env::temp_dir().join(format!("yoyogay_{}_{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+
+    for (dir, files) in [("objects", objects), ("scripts", scripts)] {
+        fs::create_dir_all(root.join(dir)).unwrap();
+        for (file, src) in files {
+            fs::write(root.join(dir).join(file), src).unwrap();
+        }
+    }
+
+    root
+}
+
+#[test]
+fn reports_errors_from_every_object() {
+    let root = project_with_objects(
+        "errors",
+        &[
+            (
+                "obj_a.yyg",
+                "#id obj_a\n#event create\nx = ;\ny = 1 +\n#event step\nz = ;",
+            ),
+            (
+                "obj_b.yyg",
+                "#id obj_b\n#event create\nhp = 1;\n\nspeed = * 2;",
+            ),
+        ],
+    );
+
+    let err = check_error(&root);
+    let OrganizeError::ObjectParseErrors(errors) = err else {
+        panic!("Expected object errors, found {:?}", err);
+    };
+
+    let mut found: Vec<(String, usize)> = errors
+        .iter()
+        .map(|(path, err)| {
+            let file = path.file_name().unwrap().to_string_lossy().to_string();
+            match err {
+                ObjectParseError::ParseError(err) => (file, err.text_range.start.line),
+                _ => panic!("Expected a parse error, found {:?}", err),
+            }
+        })
+        .collect();
+    found.sort();
+
+    assert_eq!(
+        found,
+        vec![
+            ("obj_a.yyg".to_string(), 3),
+            ("obj_a.yyg".to_string(), 4),
+            ("obj_a.yyg".to_string(), 6),
+            ("obj_b.yyg".to_string(), 5),
+        ]
+    );
+
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn checks_names_against_every_object() {
+    let root = project_with_objects(
+        "names",
+        &[
+            (
+                "obj_a.yyg",
+                "#id obj_a\n#event create\nvar mana = 3;\n#event step\nif place_meeting(x, y, obj_b) {\n    hp -= mana;\n}",
+            ),
+            ("obj_b.yyg", "#id obj_b\n#event create\ninstance_create(x, y, obj_a);"),
+        ],
+    );
+
+    let err = check_error(&root);
+    let OrganizeError::ObjectParseErrors(errors) = err else {
+        panic!("Expected object errors, found {:?}", err);
+    };
+
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].0.ends_with("obj_a.yyg"));
+    assert!(matches!(
+        &errors[0].1,
+        ObjectParseError::ResolveError(err) if err.text_range.start.line == 6
+    ));
+
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn listed_assets_are_names() {
+    let root = project_with_objects(
+        "assets",
+        &[(
+            "obj_player.yyg",
+            "#id obj_player\n#event create\nsprite_index = spr_player;\naudio_play_sound(snd_jump, 1, false);",
+        )],
+    );
+
+    let err = check_error(&root);
+    let OrganizeError::ObjectParseErrors(errors) = err else {
+        panic!("Expected object errors, found {:?}", err);
+    };
+    assert_eq!(errors.len(), 2);
+
+    fs::write(
+        root.join("assets.txt"),
+        "// Made in the IDE\nspr_player\n\nsnd_jump\n",
+    )
+    .unwrap();
+    let project = YoyogayProject::create_from_directory(&root).unwrap();
+    assert_eq!(project.assets, ["spr_player", "snd_jump"]);
+    project.check(Lints::default()).unwrap();
+
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn shadowed_names_are_warnings() {
+    let root = project_with_objects(
+        "shadowed",
+        &[(
+            "obj_player.yyg",
+            "#id obj_player\n#event create\nfn move_to(x, y) {\n    speed = 1;\n}",
+        )],
+    );
+
+    let project = YoyogayProject::create_from_directory(&root).unwrap();
+    let checked = project.check(Lints::default()).unwrap();
+    let names: Vec<&ResolveErrorData> = checked
+        .warnings
+        .iter()
+        .filter_map(|(_, warning)| match warning {
+            Warning::Resolve(err) => Some(&err.data),
+            Warning::Lint(_) => None,
+        })
+        .collect();
+    assert_eq!(
+        names,
+        [
+            &ResolveErrorData::ShadowedName("x".to_string()),
+            &ResolveErrorData::ShadowedName("y".to_string())
+        ]
+    );
+
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn project_info_lists_declarations() {
+    let root = project_with_objects(
+        "info",
+        &[
+            (
+                "obj_player.yyg",
+                "#id obj_player\n#event create\nvar boost: Real = 2;\nfn heal(amount: Real) {}\nglobal.lives = 3;\nlet local = 1;",
+            ),
+            ("obj_enemy.yyg", "#id obj_enemy\n#event step\nobj_player::boost += global.lives;\nobj_player::heal(1);"),
+        ],
+    );
+
+    let project = YoyogayProject::create_from_directory(&root).unwrap();
+    let checked = project.check(Lints::default()).unwrap();
+    let info = &checked.info;
+
+    assert_eq!(info.members_of("obj_player"), vec!["boost", "heal"]);
+    assert!(info.members_of("obj_enemy").is_empty());
+    assert!(matches!(info.lookup("obj_enemy"), Some(Symbol::Object(_))));
+    assert!(
+        matches!(info.lookup("lives"), Some(Symbol::Global(location)) if location.text_range.start.line == 5)
+    );
+    assert!(info.lookup("local").is_none());
+
+    let Some(Member::Field(boost)) = info.object("obj_player").unwrap().member("boost") else {
+        panic!("Expected boost to be a field");
+    };
+    assert!(boost.location.path.ends_with("obj_player.yyg"));
+    assert_eq!(boost.location.text_range.start.line, 3);
+
+    let location = &info.object("obj_enemy").unwrap().location;
+    assert!(location.path.ends_with("obj_enemy.yyg"));
+    assert_eq!(
+        (location.text_range.start.pos, location.text_range.end.pos),
+        (5, 13)
+    );
+
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn cross_object_members_are_typed() {
+    let root = project_with_objects(
+        "members",
+        &[
+            (
+                "obj_player.yyg",
+                "#id obj_player\n#event create\nvar boost: Real = 2;",
+            ),
+            (
+                "obj_enemy.yyg",
+                "#id obj_enemy\n#event step\nobj_player::boost = \"fast\";",
+            ),
+        ],
+    );
+
+    let err = check_error(&root);
+    let OrganizeError::ObjectParseErrors(errors) = err else {
+        panic!("Expected object errors, found {:?}", err);
+    };
+
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        &errors[0].1,
+        ObjectParseError::TypeError(err) if matches!(err.data, TypeErrorData::MismatchedTypes(..))
+    ));
+
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn scripts_declare_global_functions() {
+    let root = project_with_files(
+        "scripts",
+        &[(
+            "obj_player.yyg",
+            "#id obj_player\n#event create\nvar hp: Real = clamp_hp(12);\nlet total = add(hp, 1);",
+        )],
+        &[(
+            "scr_health.yyg",
+            "fn clamp_hp(value: Real) -> Real {\n    return clamp(value, 0, MAX_HP());\n}\nfn MAX_HP() -> Real {\n    return 10;\n}\nfn add(a, b) {\n    return a + b;\n}",
+        )],
+    );
+
+    let project = YoyogayProject::create_from_directory(&root).unwrap();
+    let checked = project.check(Lints::default()).unwrap();
+    assert_eq!(project.scripts.len(), 1);
+    assert_eq!(project.scripts[0].id, "scr_health");
+
+    let info = &checked.info;
+    assert!(matches!(info.lookup("clamp_hp"), Some(Symbol::Function(_))));
+
+    let gamemaker_project = GameMakerProject::new_from_yoyogay_project(&checked).unwrap();
+    let output = root.join("output");
+    gamemaker_project.write_in_fs(&output).unwrap();
+
+    let src = fs::read_to_string(output.join("scripts/scr_health/scr_health.gml")).unwrap();
+    assert!(src.contains("function clamp_hp(value)"));
+    let yy = fs::read_to_string(output.join("scripts/scr_health/scr_health.yy")).unwrap();
+    assert!(yy.contains("\"resourceType\":\"GMScript\","));
+
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn scripts_declare_global_constants() {
+    let root = project_with_files(
+        "constants",
+        &[(
+            "obj_player.yyg",
+            "#id obj_player\n#event create\nvar hp: Real = MAX_HP;",
+        )],
+        &[(
+            "scr_config.yyg",
+            "const MAX_HP: Real = 10;\nfn f() {\n    const LOCAL = 1;\n}",
+        )],
+    );
+
+    let project = YoyogayProject::create_from_directory(&root).unwrap();
+    let checked = project.check(Lints::default()).unwrap();
+
+    let info = &checked.info;
+    let Some(Symbol::Constant(constant)) = info.lookup("MAX_HP") else {
+        panic!("Expected MAX_HP to be a constant");
+    };
+    assert!(constant.location.path.ends_with("scr_config.yyg"));
+    assert_eq!(constant.location.text_range.start.line, 1);
+    assert!(info.lookup("LOCAL").is_none());
+
+    let gamemaker_project = GameMakerProject::new_from_yoyogay_project(&checked).unwrap();
+    let output = root.join("output");
+    gamemaker_project.write_in_fs(&output).unwrap();
+    let src = fs::read_to_string(output.join("scripts/scr_config/scr_config.gml")).unwrap();
+    assert!(src.starts_with("#macro MAX_HP (10)\n"), "{}", src);
+    fs::remove_dir_all(output).unwrap();
+
+    fs::write(
+        root.join("objects/obj_player.yyg"),
+        "#id obj_player\n#event create\nMAX_HP = 5;",
+    )
+    .unwrap();
+    let err = check_error(&root);
+    let OrganizeError::ObjectParseErrors(errors) = err else {
+        panic!("Expected object errors, found {:?}", err);
+    };
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        &errors[0].1,
+        ObjectParseError::ResolveError(err) if matches!(err.data, ResolveErrorData::ConstantReassignment(_))
+    ));
+
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn script_errors_are_reported() {
+    let root = project_with_files(
+        "script_errors",
+        &[(
+            "obj_player.yyg",
+            "#id obj_player\n#event create\nvar hp: String = half(2);",
+        )],
+        &[
+            (
+                "scr_math.yyg",
+                "fn half(value: Real) -> Real {\n    return valeu / 2;\n}",
+            ),
+            ("scr-bad.yyg", ""),
+        ],
+    );
+
+    let err = check_error(&root);
+    let OrganizeError::ScriptParseErrors(errors) = err else {
+        panic!("Expected script errors, found {:?}", err);
+    };
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(&errors[0].1, ScriptParseError::InvalidName(name) if name == "scr-bad"));
+
+    fs::remove_file(root.join("scripts/scr-bad.yyg")).unwrap();
+    let err = check_error(&root);
+    let OrganizeError::ScriptParseErrors(errors) = err else {
+        panic!("Expected script errors, found {:?}", err);
+    };
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].0.ends_with("scr_math.yyg"));
+    assert!(matches!(
+        &errors[0].1,
+        ScriptParseError::ResolveError(err) if err.text_range.start.line == 2
+    ));
+
+    fs::write(
+        root.join("scripts/scr_math.yyg"),
+        "fn half(value: Real) -> Real {\n    return value / 2;\n}",
+    )
+    .unwrap();
+    let err = check_error(&root);
+    let OrganizeError::ObjectParseErrors(errors) = err else {
+        panic!("Expected object errors, found {:?}", err);
+    };
+    assert!(matches!(
+        &errors[0].1,
+        ObjectParseError::TypeError(err) if matches!(err.data, TypeErrorData::MismatchedTypes(..))
+    ));
+
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn objects_have_any_event() {
+    let root = project_with_objects(
+        "events",
+        &[(
+            "obj_player.yyg",
+            "#id obj_player\n#event create\nvar hp = 3;\n#event alarm[0]\nhp -= 1;\n#event key_press vk_space\nhp += 1;\n#event key_press 32\nhp = 0;\n#event alarm[12]\nhp = 1;",
+        )],
+    );
+
+    let err = check_error(&root);
+    let OrganizeError::ObjectParseErrors(errors) = err else {
+        panic!("Expected object errors, found {:?}", err);
+    };
+    let errors: Vec<String> = errors.iter().map(|(_, err)| format!("{:?}", err)).collect();
+    assert_eq!(
+        errors,
+        vec![
+            "DuplicateEvent(\"key_press 32\", 8)",
+            "UnknownEvent(\"alarm[12]\", 10)"
+        ]
+    );
+
+    fs::write(
+        root.join("objects/obj_player.yyg"),
+        "#id obj_player\n#event create\nvar hp = 3;\n#event alarm[0]\nhp -= 1;\n#event key_press vk_space\nhp += 1;",
+    )
+    .unwrap();
+    let project = YoyogayProject::create_from_directory(&root).unwrap();
+    project.check(Lints::default()).unwrap();
+    let files: Vec<String> = project.objects[0]
+        .events
+        .iter()
+        .map(|event| event.event.file_name())
+        .collect();
+    assert_eq!(
+        files,
+        vec!["Create_0.gml", "Alarm_0.gml", "KeyPress_32.gml"]
+    );
+
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn collision_events_type_other() {
+    let root = project_with_objects(
+        "collision",
+        &[
+            (
+                "obj_player.yyg",
+                "#id obj_player\n#event collision obj_enemy\nother.hp -= 1;\n#event collision obj_ghost\nx = 0;",
+            ),
+            ("obj_enemy.yyg", "#id obj_enemy\n#event create\nvar hp: Real = 3;"),
+        ],
+    );
+
+    let err = check_error(&root);
+    let OrganizeError::ObjectParseErrors(errors) = err else {
+        panic!("Expected object errors, found {:?}", err);
+    };
+    let errors: Vec<String> = errors.iter().map(|(_, err)| format!("{:?}", err)).collect();
+    assert_eq!(errors, vec!["UnknownCollisionObject(\"obj_ghost\", 4)"]);
+
+    fs::write(
+        root.join("objects/obj_player.yyg"),
+        "#id obj_player\n#event collision obj_enemy\nother.hp = \"dead\";\nother.hpp = 0;\n#event step\nother.hpp = 0;",
+    )
+    .unwrap();
+    let err = check_error(&root);
+    let OrganizeError::ObjectParseErrors(errors) = err else {
+        panic!("Expected object errors, found {:?}", err);
+    };
+    let errors: Vec<String> = errors.iter().map(|(_, err)| format!("{:?}", err)).collect();
+    assert_eq!(errors.len(), 2, "{:?}", errors);
+    assert!(errors[0].contains("UnknownMember(\"other\", \"hpp\", Some(\"hp\"))"));
+    assert!(errors[1].contains("MismatchedTypes(Real, String)"));
+
+    fs::write(
+        root.join("objects/obj_player.yyg"),
+        "#id obj_player\n#event collision obj_enemy\nother.hp -= 1;",
+    )
+    .unwrap();
+    let project = YoyogayProject::create_from_directory(&root).unwrap();
+    let checked = project.check(Lints::default()).unwrap();
+    let gamemaker_project = GameMakerProject::new_from_yoyogay_project(&checked).unwrap();
+    let output = root.join("output");
+    gamemaker_project.write_in_fs(&output).unwrap();
+
+    let player = output.join("objects/obj_player");
+    assert!(player.join("Collision_obj_enemy.gml").is_file());
+    let yy = fs::read_to_string(player.join("obj_player.yy")).unwrap();
+    assert!(yy.contains(
+        "\"collisionObjectId\":{\"name\":\"obj_enemy\",\"path\":\"objects/obj_enemy/obj_enemy.yy\",},\"eventNum\":0,\"eventType\":4,"
+    ));
+
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn header_directives() {
+    let root = project_with_objects(
+        "directives",
+        &[
+            (
+                "obj_player.yyg",
+                "#id obj_player\n#parent obj_ghost\n#sprite spr-player\n#visible maybe\n#depth -10\n#depth 3\n#layer Instances\n#event create\nvar hp = 3;",
+            ),
+            (
+                "obj_base.yyg",
+                "#id obj_base\n#event create\nvar base_speed = 1;",
+            ),
+        ],
+    );
+
+    let err = check_error(&root);
+    let OrganizeError::ObjectParseErrors(errors) = err else {
+        panic!("Expected object errors, found {:?}", err);
+    };
+    let errors: Vec<String> = errors.iter().map(|(_, err)| format!("{:?}", err)).collect();
+    assert_eq!(
+        errors,
+        vec![
+            "InvalidDirective(\"sprite\", \"spr-player\", 3)",
+            "InvalidDirective(\"visible\", \"maybe\", 4)",
+            "DuplicateDirective(\"depth\", 6)",
+            "UnknownDirective(\"layer\", 7)",
+        ]
+    );
+
+    fs::write(
+        root.join("objects/obj_player.yyg"),
+        "#id obj_player\n#parent obj_ghost\n#event create\nvar hp = 3;",
+    )
+    .unwrap();
+    let err = check_error(&root);
+    let OrganizeError::ObjectParseErrors(errors) = err else {
+        panic!("Expected object errors, found {:?}", err);
+    };
+    assert!(matches!(
+        &errors[0].1,
+        ObjectParseError::UnknownParent(parent, 2) if parent == "obj_ghost"
+    ));
+
+    fs::write(
+        root.join("objects/obj_player.yyg"),
+        "#id obj_player\n#parent obj_base\n#sprite spr_player\n#mask spr_player_mask\n#visible false\n#persistent true\n#solid true\n#depth -10\n#event step\nx += base_speed;",
+    )
+    .unwrap();
+    let project = YoyogayProject::create_from_directory(&root).unwrap();
+    let checked = project.check(Lints::default()).unwrap();
+    let gamemaker_project = GameMakerProject::new_from_yoyogay_project(&checked).unwrap();
+    let output = root.join("output");
+    gamemaker_project.write_in_fs(&output).unwrap();
+
+    let player = output.join("objects/obj_player");
+    let yy = fs::read_to_string(player.join("obj_player.yy")).unwrap();
+    for expected in [
+        "  \"parentObjectId\":{\n    \"name\":\"obj_base\",\n    \"path\":\"objects/obj_base/obj_base.yy\",\n  },\n",
+        "  \"persistent\":true,\n",
+        "  \"solid\":true,\n",
+        "  \"spriteId\":{\n    \"name\":\"spr_player\",\n",
+        "  \"spriteMaskId\":{\n    \"name\":\"spr_player_mask\",\n",
+        "  \"visible\":false,\n",
+    ] {
+        assert!(yy.contains(expected), "{}", expected);
+    }
+    let create = fs::read_to_string(player.join("Create_0.gml")).unwrap();
+    assert_eq!(create, "event_inherited();\ndepth = -10;\n");
+
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn parent_chains() {
+    let root = project_with_files(
+        "inheritance",
+        &[
+            (
+                "obj_a.yyg",
+                "#id obj_a\n#parent obj_c\n#event create\nsuper();\nvar hp = 3;",
+            ),
+            ("obj_b.yyg", "#id obj_b\n#parent obj_a"),
+            ("obj_c.yyg", "#id obj_c\n#parent obj_b"),
+            ("obj_d.yyg", "#id obj_d\n#parent obj_a"),
+            (
+                "obj_e.yyg",
+                "#id obj_e\n#event step\nif x == 0 {\n    super();\n}",
+            ),
+        ],
+        &[("scr_util.yyg", "fn reset() {\n    super();\n}")],
+    );
+
+    let err = check_error(&root);
+    let OrganizeError::ScriptParseErrors(errors) = err else {
+        panic!("Expected script errors, found {:?}", err);
+    };
+    assert!(matches!(
+        errors[0].1,
+        ScriptParseError::SuperOutsideObject(2)
+    ));
+
+    fs::remove_file(root.join("scripts/scr_util.yyg")).unwrap();
+    let err = check_error(&root);
+    let OrganizeError::ObjectParseErrors(errors) = err else {
+        panic!("Expected object errors, found {:?}", err);
+    };
+    let mut errors: Vec<String> = errors.iter().map(|(_, err)| format!("{:?}", err)).collect();
+    errors.sort();
+    assert_eq!(
+        errors,
+        vec![
+            "ParentCycle([\"obj_a\", \"obj_c\", \"obj_b\"], 2)",
+            "ParentCycle([\"obj_b\", \"obj_a\", \"obj_c\"], 2)",
+            "ParentCycle([\"obj_c\", \"obj_b\", \"obj_a\"], 2)",
+            "SuperWithoutParent(4)",
+        ]
+    );
+
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn missing_super_lint() {
+    let root = project_with_objects(
+        "missing_super",
+        &[
+            ("obj_base.yyg", "#id obj_base\n#event create\nvar hp = 3;"),
+            (
+                "obj_player.yyg",
+                "#id obj_player\n#parent obj_base\n#event create\nhp = 5;",
+            ),
+            (
+                "obj_enemy.yyg",
+                "#id obj_enemy\n#parent obj_base\n#event create\nsuper();\nhp = 1;",
+            ),
+        ],
+    );
+
+    let project = YoyogayProject::create_from_directory(&root).unwrap();
+    let checked = project.check(Lints::default()).unwrap();
+    assert!(checked.warnings.is_empty());
+
+    let lints = Lints {
+        missing_super_create: true,
+    };
+    let checked = project.check(lints).unwrap();
+    let warnings: Vec<(String, &Warning)> = checked
+        .warnings
+        .iter()
+        .map(|(path, warning)| {
+            (
+                path.file_name().unwrap().to_string_lossy().to_string(),
+                warning,
+            )
+        })
+        .collect();
+    assert_eq!(
+        warnings,
+        vec![(
+            "obj_player.yyg".to_string(),
+            &Warning::Lint(ObjectWarning::MissingSuperCall(3))
+        )]
+    );
+
+    let gamemaker_project = GameMakerProject::new_from_yoyogay_project(&checked).unwrap();
+    let output = root.join("output");
+    gamemaker_project.write_in_fs(&output).unwrap();
+    let create = fs::read_to_string(output.join("objects/obj_enemy/Create_0.gml")).unwrap();
+    assert_eq!(create, "event_inherited();\nhp = 1;\n");
+
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn depth_is_set_after_the_parent_create() {
+    let root = project_with_objects(
+        "depth",
+        &[
+            ("obj_base.yyg", "#id obj_base\n#event create\nvar hp = 3;"),
+            (
+                "obj_player.yyg",
+                "#id obj_player\n#parent obj_base\n#depth 5\n#event create\nx = 1;\nsuper();\nhp = 1;",
+            ),
+            (
+                "obj_enemy.yyg",
+                "#id obj_enemy\n#parent obj_base\n#depth 6\n#event create\nif x == 0 {\n    super();\n}\nhp = 2;",
+            ),
+            ("obj_wall.yyg", "#id obj_wall\n#depth 7\n#event create\nvar hp = 9;"),
+        ],
+    );
+
+    let project = YoyogayProject::create_from_directory(&root).unwrap();
+    let checked = project.check(Lints::default()).unwrap();
+    let gamemaker_project = GameMakerProject::new_from_yoyogay_project(&checked).unwrap();
+    let output = root.join("output");
+    gamemaker_project.write_in_fs(&output).unwrap();
+
+    let create = |object: &str| {
+        fs::read_to_string(output.join(format!("objects/{}/Create_0.gml", object))).unwrap()
+    };
+    assert_eq!(
+        create("obj_player"),
+        "x = 1;\nevent_inherited();\ndepth = 5;\nhp = 1;\n"
+    );
+    assert_eq!(
+        create("obj_enemy"),
+        "if (x == 0) {\n    event_inherited();\n}\nhp = 2;\ndepth = 6;\n"
+    );
+    assert_eq!(create("obj_wall"), "depth = 7;\nhp = 9;\n");
+
+    fs::remove_dir_all(root).unwrap();
+}
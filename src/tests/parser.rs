@@ -0,0 +1,249 @@
+use std::path::Path;
+
+use crate::{
+    ast::NodeData,
+    organizer::info::YoyogayFunction,
+    parser::{expr::parse_expr, parse_tks, types::YoyogayType},
+    tokenizer::tokenize,
+};
+
+fn parsed(src: &str) -> String {
+    let tokens = tokenize(src).unwrap();
+    let node = parse_expr(&tokens).unwrap();
+
+    node.to_string()
+}
+
+#[test]
+fn precedence_and_associativity() {
+    assert_eq!(parsed("a - b - c"), "((a) - (b)) - (c)");
+    assert_eq!(parsed("a + b * c"), "(a) + ((b) * (c))");
+    assert_eq!(parsed("a || b && c == d"), "(a) || ((b) && ((c) == (d)))");
+    assert_eq!(parsed("x & 1 == 0"), "((x) & (1)) == (0)");
+}
+
+#[test]
+fn unary_operators_anywhere() {
+    assert_eq!(parsed("a * -b"), "(a) * ((-b))");
+    assert_eq!(parsed("-a.b[0]"), "(-a.b[0])");
+    assert_eq!(parsed("!a && ~b"), "((!a)) && ((~b))");
+    assert_eq!(parsed("- - a"), "(-(-a))");
+}
+
+#[test]
+fn postfix_chains() {
+    assert_eq!(parsed("obj.list[i + 1].get(a, b)(c)"), "obj.list[(i) + (1)].get(a, b)(c)");
+    assert_eq!(parsed("obj_player::speed"), "obj_player.speed");
+    assert_eq!(parsed("f()"), "f()");
+}
+
+#[test]
+fn tuples_and_grouping() {
+    assert_eq!(parsed("a, (b, c)"), "(a, (b, c))");
+    assert_eq!(parsed("(a + b) * c"), "((a) + (b)) * (c)");
+}
+
+#[test]
+fn malformed_expressions_are_errors() {
+    for src in ["a +", "(a", "f(a,", "a.1", "a b", "arr[0"] {
+        let tokens = tokenize(src).unwrap();
+        assert!(parse_expr(&tokens).is_err(), "{} should not parse", src);
+    }
+}
+
+#[test]
+fn long_expressions() {
+    let src = vec!["x"; 4000].join(" + ");
+    let tokens = tokenize(&src).unwrap();
+
+    assert!(parse_expr(&tokens).is_ok());
+}
+
+#[test]
+fn recovers_after_errors() {
+    let src = "var a = ;\nb = 1;\nfn f() {\n    c = + ;\n    d = 2;\n}\nif {}\ne = 3;";
+    let tokens = tokenize(src).unwrap();
+    let (program, errors) = parse_tks(&tokens);
+
+    let lines: Vec<usize> = errors.iter().map(|err| err.text_range.start.line).collect();
+    assert_eq!(lines, vec![1, 4, 7]);
+    assert_eq!(
+        program.to_string(),
+        "<error>\n\nb = 1;\n\nfn f() {\n    <error>\n    \n    d = 2;\n}\n\n<error>\n\ne = 3;"
+    );
+}
+
+#[test]
+fn resyncs_on_statement_keywords() {
+    let tokens = tokenize("x = 1 +\nconst y = 2;").unwrap();
+    let (program, errors) = parse_tks(&tokens);
+
+    assert_eq!(errors.len(), 1);
+    assert_eq!(program.to_string(), "<error>\n\nconst y = 2;");
+}
+
+#[test]
+fn expression_statements() {
+    let src = "show_debug_message(hp);\ninstance_destroy();\nobj.method().next[0];";
+    let tokens = tokenize(src).unwrap();
+    let (program, errors) = parse_tks(&tokens);
+
+    assert!(errors.is_empty(), "{:?}", errors);
+    assert_eq!(
+        program.to_string(),
+        "show_debug_message(hp);\n\ninstance_destroy();\n\nobj.method().next[0];"
+    );
+}
+
+#[test]
+fn place_expression_targets() {
+    let src = "self.hp -= 1;\narr[i] = f(a == b);\nother.speed *= 2;\na.b, c[0] = c[0], a.b;";
+    let tokens = tokenize(src).unwrap();
+    let (program, errors) = parse_tks(&tokens);
+
+    assert!(errors.is_empty(), "{:?}", errors);
+    assert_eq!(
+        program.to_string(),
+        "self.hp -= 1;\n\narr[i] = f((a) == (b));\n\nother.speed *= 2;\n\n(a.b, c[0]) = (c[0], a.b);"
+    );
+}
+
+#[test]
+fn loop_control_outside_loops() {
+    let src = "break;\nwhile a {\n    fn f() {\n        continue;\n    }\n    if b {\n        break;\n    }\n}";
+    let tokens = tokenize(src).unwrap();
+    let (_, errors) = parse_tks(&tokens);
+
+    let found: Vec<(String, usize)> = errors
+        .iter()
+        .map(|err| (format!("{:?}", err.data), err.text_range.start.line))
+        .collect();
+    assert_eq!(
+        found,
+        vec![
+            ("BreakOutsideLoop".to_string(), 1),
+            ("ContinueOutsideLoop".to_string(), 4),
+        ]
+    );
+}
+
+#[test]
+fn ranges_only_in_for_headers() {
+    let tokens = tokenize("for i in (a + 1..b * 2).step(k) {}").unwrap();
+    let (program, errors) = parse_tks(&tokens);
+    assert!(errors.is_empty(), "{:?}", errors);
+    assert_eq!(program.to_string(), "for i in ((a) + (1)..(b) * (2)).step(k) {\n    \n}");
+
+    for src in [
+        "x = 0..1;",
+        "for i in f(0..1) {}",
+        "for i in arr[0..1] {}",
+        "for i in 0..n..m {}",
+        "for x in (0..1, 2) {}",
+        "for x in (2, 0..1) {}",
+        "for x in (0..1).step(2).step(3) {}",
+        "for x in (0..1) + 2 {}",
+        "for x in -(0..1) {}",
+        "for x in 0..(1..2) {}",
+    ] {
+        let tokens = tokenize(src).unwrap();
+        assert!(!parse_tks(&tokens).1.is_empty(), "{} should not parse", src);
+    }
+}
+
+#[test]
+fn returns_and_return_types() {
+    let src = "fn clamp_hp(hp, max) -> Real {\n    if hp > max {\n        return max;\n    }\n    return hp;\n}\nfn reset() {\n    return;\n}";
+    let tokens = tokenize(src).unwrap();
+    let (program, errors) = parse_tks(&tokens);
+    assert!(errors.is_empty(), "{:?}", errors);
+
+    let NodeData::Program(statements) = &*program.data else {
+        panic!("Expected a program");
+    };
+    let clamp_hp = YoyogayFunction::from_declaration(&statements[0], Path::new("f.yyg")).unwrap();
+    let params: Vec<&str> = clamp_hp.params.iter().map(|p| p.name.as_str()).collect();
+    assert_eq!(params, vec!["hp", "max"]);
+    assert_eq!(clamp_hp.return_type.map(|t| t.to_string()), Some("Real".to_string()));
+    assert!(YoyogayFunction::from_declaration(&statements[1], Path::new("f.yyg")).unwrap().return_type.is_none());
+}
+
+#[test]
+fn every_path_must_return() {
+    let cases = [
+        ("fn f() -> Real {\n    if a {\n        return 1;\n    }\n}", vec!["MissingReturn"]),
+        ("fn f() -> Real {\n    if a {\n        return 1;\n    } else {\n        return 2;\n    }\n}", vec![]),
+        ("fn f() -> Real {\n    loop {\n        return 1;\n    }\n}", vec![]),
+        ("fn f() -> Real {\n    loop {\n        break;\n    }\n}", vec!["MissingReturn"]),
+        ("fn f() -> Real {\n    while a {\n        return 1;\n    }\n}", vec!["MissingReturn"]),
+        ("fn f() -> Real {\n    if a {\n        return;\n    }\n    return 1;\n}", vec!["MissingReturnValue"]),
+        ("fn f() {\n    if a {\n        return;\n    }\n}", vec![]),
+        ("fn f() -> Real {\n    do {\n        if c {\n            break;\n        }\n        return 1;\n    } until true;\n}", vec!["MissingReturn"]),
+        ("fn f() -> Real {\n    do {\n        return 1;\n    } until true;\n}", vec![]),
+        ("fn f() -> Real {\n    while a {\n        if b {\n            break;\n        }\n    }\n    return 1;\n}", vec![]),
+        ("fn f() -> Void {}", vec![]),
+        ("fn f() -> Void {\n    if a {\n        return;\n    }\n}", vec![]),
+    ];
+
+    for (src, expected) in cases {
+        let tokens = tokenize(src).unwrap();
+        let (_, errors) = parse_tks(&tokens);
+        let found: Vec<String> = errors.iter().map(|err| format!("{:?}", err.data)).collect();
+
+        assert_eq!(found, expected, "{}", src);
+    }
+}
+
+#[test]
+fn else_if_chains() {
+    let tokens = tokenize("if a {\n    x = 1;\n} else if b {\n    x = 2;\n}\ny = 3;").unwrap();
+    let (program, errors) = parse_tks(&tokens);
+    assert!(errors.is_empty(), "{:?}", errors);
+
+    assert_eq!(
+        program.to_string(),
+        "if a {\n    x = 1;\n} else if b {\n    x = 2;\n}\n\ny = 3;"
+    );
+
+    let tokens = tokenize("fn f() -> Real {\n    if a {\n        return 1;\n    } else if b {\n        return 2;\n    }\n}").unwrap();
+    let errors: Vec<String> = parse_tks(&tokens).1.iter().map(|err| format!("{:?}", err.data)).collect();
+    assert_eq!(errors, vec!["MissingReturn"]);
+}
+
+#[test]
+fn type_annotations() {
+    let src = "var x: Real = 1;\nconst p: (Real, Real) = 1, 2;\nfn f(a: String, b: [Real],) -> Bool {\n    return true;\n}";
+    let tokens = tokenize(src).unwrap();
+    let (program, errors) = parse_tks(&tokens);
+    assert!(errors.is_empty(), "{:?}", errors);
+
+    assert_eq!(
+        program.to_string(),
+        "var x: Real = 1;\n\nconst p: (Real, Real) = (1, 2);\n\nfn f(a: String, b: [Real]) -> Bool {\n    return true;\n}"
+    );
+}
+
+#[test]
+fn generic_types() {
+    let cases = [
+        ("Real", "Real"),
+        ("(Real)", "Real"),
+        ("(Real,)", "(Real)"),
+        ("Map<String, [Real]>", "Map<String, [Real]>"),
+        ("Map<String, Array<Real>>", "Map<String, Array<Real>>"),
+        ("Array<Array<Array<Real>>>", "Array<Array<Array<Real>>>"),
+        ("[(Real, Map<A, B>)]", "[(Real, Map<A, B>)]"),
+    ];
+
+    for (src, expected) in cases {
+        let tokens = tokenize(src).unwrap();
+        let parsed = YoyogayType::create_from_tokens(&tokens).ok().map(|t| t.to_string());
+
+        assert_eq!(parsed.as_deref(), Some(expected), "{}", src);
+    }
+
+    for src in ["", "Array<Real", "Array<Real>>", "[Real", "[Array<Real>>]", "Map<,>", "Real Real"] {
+        let tokens = tokenize(src).unwrap();
+        assert!(YoyogayType::create_from_tokens(&tokens).is_err(), "{} should not parse", src);
+    }
+}
@@ -0,0 +1,173 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use crate::{
+    checker::resolver::{resolve_names, Binding, BindingKind, ResolveError},
+    gamemaker::{event::GameMakerEvent, ObjectProperties},
+    organizer::{
+        info::ProjectInfo,
+        object::{Object, ObjectEvent, ParsedObject},
+    },
+    parser::parse_tks,
+    tokenizer::tokenize,
+};
+
+fn resolved(src: &str) -> (Vec<Binding>, Vec<ResolveError>) {
+    resolved_with_fields(src, &[])
+}
+
+fn resolved_with_fields(src: &str, fields: &[String]) -> (Vec<Binding>, Vec<ResolveError>) {
+    let tokens = tokenize(src).unwrap();
+    let (program, errors) = parse_tks(&tokens);
+    assert!(errors.is_empty(), "{:?}", errors);
+
+    let objects = [Object {
+        id: "obj_wall".to_string(),
+        path: PathBuf::from("obj_wall.yyg"),
+        properties: ObjectProperties::default(),
+        directive_lines: HashMap::new(),
+        events: vec![ObjectEvent {
+            event: GameMakerEvent::CREATE,
+            line: 2,
+            tokens: tokenize("var hp = 3;\nglobal.walls = 0;").unwrap(),
+        }],
+    }];
+    let info = ProjectInfo::from_objects(&[ParsedObject::parse(&objects[0])]);
+    resolve_names(&program, &info, fields, None)
+}
+
+fn error_names(errors: &[ResolveError]) -> Vec<String> {
+    errors.iter().map(|err| format!("{:?}", err.data)).collect()
+}
+
+#[test]
+fn binds_every_kind_of_name() {
+    let (bindings, errors) = resolved(
+        "let hp = 10;
+        fn heal(amount) {
+            hp += amount;
+        }
+        heal(1);
+        if place_meeting(x, y + 1, obj_wall) {}",
+    );
+    let found: Vec<(&str, BindingKind)> = bindings
+        .iter()
+        .map(|binding| (binding.name.as_str(), binding.kind))
+        .collect();
+
+    assert!(errors.is_empty(), "{:?}", errors);
+    assert_eq!(
+        found,
+        vec![
+            ("amount", BindingKind::Parameter),
+            ("hp", BindingKind::Local),
+            ("heal", BindingKind::Function),
+            ("place_meeting", BindingKind::Builtin),
+            ("x", BindingKind::InstanceField),
+            ("y", BindingKind::InstanceField),
+            ("obj_wall", BindingKind::Global),
+        ]
+    );
+}
+
+#[test]
+fn undefined_names_suggest_close_ones() {
+    let (_, errors) =
+        resolved("var health = 10;\nhelth -= 1;\nfoo = 2;\nshow_debug_mesage(health);");
+
+    assert_eq!(
+        error_names(&errors),
+        vec![
+            "UndefinedName(\"helth\", Some(\"health\"))",
+            "UndefinedName(\"foo\", None)",
+            "UndefinedName(\"show_debug_mesage\", Some(\"show_debug_message\"))",
+        ]
+    );
+    assert_eq!(errors[0].text_range.start.line, 2);
+}
+
+#[test]
+fn names_are_scoped_to_their_block() {
+    let (_, errors) = resolved(
+        "if true {
+            let inner = 1;
+        }
+        inner = 2;
+        for i in 0..3 {}
+        i = 0;",
+    );
+
+    assert_eq!(
+        error_names(&errors),
+        vec![
+            "UndefinedName(\"inner\", None)",
+            "UndefinedName(\"i\", Some(\"id\"))"
+        ]
+    );
+}
+
+#[test]
+fn shadowing_and_duplicates() {
+    let (_, errors) = resolved(
+        "var hp = 1;
+        var hp = 2;
+        fn heal(hp, amount, amount) {}
+        let speed = 3;",
+    );
+
+    assert_eq!(
+        error_names(&errors),
+        vec![
+            "DuplicateDeclaration(\"hp\")",
+            "ShadowedName(\"hp\")",
+            "DuplicateDeclaration(\"amount\")",
+            "ShadowedName(\"speed\")",
+        ]
+    );
+}
+
+#[test]
+fn const_let_and_var() {
+    let (_, errors) = resolved_with_fields(
+        "const MAX = 3;
+        MAX = 4;
+        mana, MAX = 1, 2;
+        if true {
+            var hp = MAX;
+            let local = 1;
+        }
+        hp -= 1;
+        mana += 1;
+        local = 2;",
+        &["mana".to_string()],
+    );
+
+    assert_eq!(
+        error_names(&errors),
+        vec![
+            "ConstantReassignment(\"MAX\")",
+            "ConstantReassignment(\"MAX\")",
+            "UndefinedName(\"local\", None)",
+        ]
+    );
+    assert_eq!(errors[0].text_range.start.line, 2);
+}
+
+#[test]
+fn members_of_other_objects() {
+    let (_, errors) = resolved(
+        "obj_wall::hp -= 1;
+        obj_wall.speed = 2;
+        obj_wall::hpp = 3;
+        global.walls += 1;
+        global.score += 1;",
+    );
+
+    assert_eq!(
+        error_names(&errors),
+        vec![
+            "UnknownMember(\"obj_wall\", \"hpp\", Some(\"hp\"))",
+            "UnknownMember(\"global\", \"score\", None)",
+        ]
+    );
+    assert_eq!(errors[0].text_range.start.line, 3);
+}
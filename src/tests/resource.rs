@@ -0,0 +1,329 @@
+use std::fs;
+
+use crate::gamemaker::{
+    event::GameMakerEvent,
+    resource::{object_yy, project_yyp, script_yy, IdeVersion, ResourceFormat},
+    GameMakerObject, GameMakerProject, GameMakerScript, ObjectProperties,
+};
+
+#[test]
+fn object_yy_layout() {
+    let name = "obj_player".to_string();
+    let properties = ObjectProperties {
+        sprite: Some("spr_player".to_string()),
+        ..Default::default()
+    };
+    let object = GameMakerObject::new(
+        &name,
+        properties,
+        vec![
+            (
+                GameMakerEvent::from_name("draw_gui").unwrap(),
+                "draw_text(0, 0, hp);\n".to_string(),
+            ),
+            (GameMakerEvent::CREATE, "hp = 3;\n".to_string()),
+        ],
+    );
+
+    assert_eq!(
+        object_yy(&object, ResourceFormat::Modern),
+        r#"{
+  "$GMObject":"",
+  "%Name":"obj_player",
+  "eventList":[
+    {"$GMEvent":"v1","%Name":"","collisionObjectId":null,"eventNum":0,"eventType":0,"isDnD":false,"name":"","resourceType":"GMEvent","resourceVersion":"2.0",},
+    {"$GMEvent":"v1","%Name":"","collisionObjectId":null,"eventNum":64,"eventType":8,"isDnD":false,"name":"","resourceType":"GMEvent","resourceVersion":"2.0",},
+  ],
+  "managed":true,
+  "name":"obj_player",
+  "overriddenProperties":[],
+  "parent":{
+    "name":"Objects",
+    "path":"folders/Objects.yy",
+  },
+  "parentObjectId":null,
+  "persistent":false,
+  "physicsAngularDamping":0.1,
+  "physicsDensity":0.5,
+  "physicsFriction":0.2,
+  "physicsGroup":1,
+  "physicsKinematic":false,
+  "physicsLinearDamping":0.1,
+  "physicsObject":false,
+  "physicsRestitution":0.1,
+  "physicsSensor":false,
+  "physicsShape":1,
+  "physicsShapePoints":[],
+  "physicsStartAwake":true,
+  "properties":[],
+  "resourceType":"GMObject",
+  "resourceVersion":"2.0",
+  "solid":false,
+  "spriteId":{
+    "name":"spr_player",
+    "path":"sprites/spr_player/spr_player.yy",
+  },
+  "spriteMaskId":null,
+  "visible":true,
+}"#
+    );
+}
+
+#[test]
+fn objects_are_written_with_their_events() {
+    let name = "obj_wall".to_string();
+    let object = GameMakerObject::new(
+        &name,
+        ObjectProperties::default(),
+        vec![
+            (
+                GameMakerEvent::from_name("step").unwrap(),
+                "x += 1;\n".to_string(),
+            ),
+            (
+                GameMakerEvent::from_name("clean_up").unwrap(),
+                "ds_list_destroy(list);\n".to_string(),
+            ),
+            (
+                GameMakerEvent::from_name("alarm[3]").unwrap(),
+                "instance_destroy();\n".to_string(),
+            ),
+            (
+                GameMakerEvent::from_name("key_press vk_space").unwrap(),
+                "jump();\n".to_string(),
+            ),
+        ],
+    );
+
+    let root = std::env::temp_dir().join(format!("yoyogay_resource_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(&root).unwrap();
+    object.write_into_fs(&root, ResourceFormat::Modern).unwrap();
+
+    let mut files: Vec<String> = fs::read_dir(root.join("obj_wall"))
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+        .collect();
+    files.sort();
+
+    assert_eq!(
+        files,
+        vec![
+            "Alarm_3.gml",
+            "CleanUp_0.gml",
+            "KeyPress_32.gml",
+            "Step_0.gml",
+            "obj_wall.yy"
+        ]
+    );
+    assert!(object_yy(&object, ResourceFormat::Modern).contains("\"eventNum\":0,\"eventType\":12,"));
+
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn legacy_object_yy() {
+    let name = "obj_wall".to_string();
+    let object = GameMakerObject::new(
+        &name,
+        ObjectProperties::default(),
+        vec![(GameMakerEvent::CREATE, "hp = 3;\n".to_string())],
+    );
+    let yy = object_yy(&object, ResourceFormat::Legacy);
+
+    assert!(yy.starts_with("{\n  \"spriteId\": null,\n  \"solid\": false,\n"));
+    assert!(yy.contains(
+        "{\"isDnD\":false,\"eventNum\":0,\"eventType\":0,\"collisionObjectId\":null,\"resourceVersion\":\"1.0\",\"name\":\"\",\"tags\":[],\"resourceType\":\"GMEvent\",}"
+    ));
+    assert!(yy.ends_with("  \"resourceVersion\": \"1.0\",\n  \"name\": \"obj_wall\",\n  \"tags\": [],\n  \"resourceType\": \"GMObject\",\n}"));
+    assert!(!yy.contains("$GMObject"));
+}
+
+fn project<'a>(
+    objects: &'a [String],
+    scripts: &'a [(String, String)],
+    ide_version: IdeVersion,
+) -> GameMakerProject<'a> {
+    GameMakerProject {
+        objects: objects
+            .iter()
+            .map(|name| GameMakerObject::new(name, ObjectProperties::default(), Vec::new()))
+            .collect(),
+        scripts: scripts
+            .iter()
+            .map(|(name, src)| GameMakerScript::new(name, src.to_string()))
+            .collect(),
+        ide_version,
+    }
+}
+
+#[test]
+fn project_yyp_layout() {
+    let objects = ["obj_player".to_string()];
+    let scripts = [("scr_math".to_string(), String::new())];
+    let project = project(&objects, &scripts, IdeVersion::new(2024, 2, 0, 163));
+
+    assert_eq!(
+        project_yyp(&project, "game"),
+        r#"{
+  "$GMProject":"",
+  "%Name":"game",
+  "AudioGroups":[
+    {"$GMAudioGroup":"v1","%Name":"audiogroup_default","exportDir":"","name":"audiogroup_default","resourceType":"GMAudioGroup","resourceVersion":"2.0","targets":-1,},
+  ],
+  "configs":{
+    "children":[],
+    "name":"Default",
+  },
+  "defaultScriptType":1,
+  "Folders":[
+    {"$GMFolder":"","%Name":"Objects","folderPath":"folders/Objects.yy","name":"Objects","resourceType":"GMFolder","resourceVersion":"2.0",},
+    {"$GMFolder":"","%Name":"Scripts","folderPath":"folders/Scripts.yy","name":"Scripts","resourceType":"GMFolder","resourceVersion":"2.0",},
+  ],
+  "IncludedFiles":[],
+  "isEcma":false,
+  "LibraryEmitters":[],
+  "MetaData":{
+    "IDEVersion":"2024.2.0.163",
+  },
+  "name":"game",
+  "resources":[
+    {"id":{"name":"obj_player","path":"objects/obj_player/obj_player.yy",},},
+    {"id":{"name":"scr_math","path":"scripts/scr_math/scr_math.yy",},},
+  ],
+  "resourceType":"GMProject",
+  "resourceVersion":"2.0",
+  "RoomOrderNodes":[],
+  "templateType":"game",
+  "TextureGroups":[
+    {"$GMTextureGroup":"","%Name":"Default","autocrop":true,"border":2,"compressFormat":"bz2","customOptions":"","directory":"","groupParent":null,"isScaled":true,"loadType":"default","mipsToGenerate":0,"name":"Default","resourceType":"GMTextureGroup","resourceVersion":"2.0","targets":-1,},
+  ],
+}"#
+    );
+}
+
+#[test]
+fn legacy_project_yyp() {
+    let objects = ["obj_player".to_string(), "obj_wall".to_string()];
+    let project = project(&objects, &[], "2022.9.1.66".parse().unwrap());
+    let yyp = project_yyp(&project, "game");
+
+    assert!(yyp.contains(
+        "{\"id\":{\"name\":\"obj_wall\",\"path\":\"objects/obj_wall/obj_wall.yy\",},\"order\":1,}"
+    ));
+    assert!(yyp.contains("  \"MetaData\": {\n    \"IDEVersion\": \"2022.9.1.66\",\n  },\n"));
+    assert!(yyp.contains("  \"resourceVersion\": \"1.6\",\n"));
+    assert!(!yyp.contains("$GMProject"));
+}
+
+#[test]
+fn ide_versions() {
+    let version: IdeVersion = "2023.8".parse().unwrap();
+
+    assert_eq!(version, IdeVersion::new(2023, 8, 0, 0));
+    assert_eq!(version.resource_format(), ResourceFormat::Modern);
+    assert_eq!(
+        IdeVersion::new(2023, 6, 0, 92).resource_format(),
+        ResourceFormat::Legacy
+    );
+    assert_eq!(IdeVersion::default().to_string(), "2024.2.0.163");
+    assert!("2024.x".parse::<IdeVersion>().is_err());
+    assert!("1.2.3.4.5".parse::<IdeVersion>().is_err());
+}
+
+#[test]
+fn projects_are_written_with_a_yyp() {
+    let objects = ["obj_player".to_string()];
+    let project = project(&objects, &[], IdeVersion::default());
+
+    let root = std::env::temp_dir().join(format!("yoyogay_project_{}", std::process::id()));
+    project.write_in_fs(&root).unwrap();
+
+    let name = root.file_name().unwrap().to_string_lossy().to_string();
+    let yyp = fs::read_to_string(root.join(format!("{}.yyp", name))).unwrap();
+    assert!(yyp.contains("objects/obj_player/obj_player.yy"));
+    assert!(root.join("objects/obj_player/obj_player.yy").is_file());
+
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn script_yy_layout() {
+    let name = "scr_math".to_string();
+    let script = GameMakerScript::new(&name, "function half(value) {}\n".to_string());
+
+    assert_eq!(
+        script_yy(&script, ResourceFormat::Modern),
+        r#"{
+  "$GMScript":"v1",
+  "%Name":"scr_math",
+  "isCompatibility":false,
+  "isDnD":false,
+  "name":"scr_math",
+  "parent":{
+    "name":"Scripts",
+    "path":"folders/Scripts.yy",
+  },
+  "resourceType":"GMScript",
+  "resourceVersion":"2.0",
+}"#
+    );
+    assert!(script_yy(&script, ResourceFormat::Legacy).contains("  \"isCompatibility\": false,\n"));
+}
+
+#[test]
+fn event_names() {
+    let events: Vec<(u32, u32, String)> = [
+        "create",
+        "begin_step",
+        "alarm[11]",
+        "user_event[2]",
+        "keyboard vk_left",
+        "key_press A",
+        "key_release 7",
+        "key_press 112",
+        "mouse left_pressed",
+        "async_http",
+        "draw_gui_end",
+        "room_start",
+    ]
+    .iter()
+    .map(|name| {
+        let event = GameMakerEvent::from_name(name).unwrap();
+        (event.event_type, event.event_num, event.file_name())
+    })
+    .collect();
+
+    assert_eq!(
+        events,
+        vec![
+            (0, 0, "Create_0.gml".to_string()),
+            (3, 1, "Step_1.gml".to_string()),
+            (2, 11, "Alarm_11.gml".to_string()),
+            (7, 12, "Other_12.gml".to_string()),
+            (5, 37, "Keyboard_37.gml".to_string()),
+            (9, 65, "KeyPress_65.gml".to_string()),
+            (10, 55, "KeyRelease_55.gml".to_string()),
+            (9, 112, "KeyPress_112.gml".to_string()),
+            (6, 4, "Mouse_4.gml".to_string()),
+            (7, 62, "Other_62.gml".to_string()),
+            (8, 75, "Draw_75.gml".to_string()),
+            (7, 4, "Other_4.gml".to_string()),
+        ]
+    );
+
+    let collision = GameMakerEvent::from_name("collision obj_enemy").unwrap();
+    assert_eq!(collision.event_type, 4);
+    assert_eq!(collision.file_name(), "Collision_obj_enemy.gml");
+
+    for name in [
+        "alarm[12]",
+        "user_event[-1]",
+        "key_press vk_nope",
+        "mouse",
+        "stpe",
+        "collision obj-enemy",
+    ] {
+        assert!(GameMakerEvent::from_name(name).is_none(), "{}", name);
+    }
+}
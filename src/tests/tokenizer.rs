@@ -0,0 +1,163 @@
+use crate::{
+    parser::parse_tks,
+    text_data::TextPos,
+    tokenizer::{tokenize, NumericLiteralKind, NumericValue, TokenData, TokenizeErrorData},
+};
+
+#[test]
+fn string_literal_escapes() {
+    let tokens = tokenize(r#"draw_text(x, y, "a\"b\\c\n\t\u{2713}");"#).unwrap();
+
+    assert_eq!(
+        tokens[6].data,
+        TokenData::StringLiteral("a\"b\\c\n\t\u{2713}".to_string())
+    );
+}
+
+#[test]
+fn multi_line_string_keeps_positions() {
+    let tokens = tokenize("x = \"one\ntwo\";\ny = 1;").unwrap();
+
+    assert_eq!(tokens[2].data, TokenData::StringLiteral("one\ntwo".to_string()));
+    assert_eq!(tokens[2].text_range.start, TextPos { line: 1, pos: 5 });
+    assert_eq!(tokens[2].text_range.end, TextPos { line: 2, pos: 4 });
+    assert_eq!(tokens[4].text_range.start, TextPos { line: 3, pos: 1 });
+}
+
+#[test]
+fn unterminated_string() {
+    let err = tokenize("x = \"abc;").unwrap_err();
+
+    assert!(matches!(err.data, TokenizeErrorData::UnterminatedString));
+    assert_eq!(err.text_range.start, TextPos { line: 1, pos: 5 });
+}
+
+#[test]
+fn invalid_escapes() {
+    let err = tokenize(r#""\q""#).unwrap_err();
+    assert!(matches!(err.data, TokenizeErrorData::InvalidEscapeSequence('q')));
+
+    let err = tokenize(r#""\u{zz}""#).unwrap_err();
+    assert!(matches!(err.data, TokenizeErrorData::InvalidUnicodeEscape(_)));
+}
+
+#[test]
+fn numeric_literal_forms() {
+    let tokens = tokenize("0.5 1e-3 0xFF 0b1010 1_000_000 $FF8000 9").unwrap();
+    let values: Vec<(NumericValue, NumericLiteralKind, &str)> = tokens
+        .iter()
+        .map(|tk| match &tk.data {
+            TokenData::NumericLiteral(num) => (num.value, num.kind, num.spelling.as_str()),
+            _ => panic!("Expected numeric literal, found {:?}", tk.data),
+        })
+        .collect();
+
+    assert_eq!(
+        values,
+        vec![
+            (NumericValue::Real(0.5), NumericLiteralKind::Decimal, "0.5"),
+            (NumericValue::Real(1e-3), NumericLiteralKind::Decimal, "1e-3"),
+            (NumericValue::Integer(255), NumericLiteralKind::Hex, "0xFF"),
+            (NumericValue::Integer(10), NumericLiteralKind::Binary, "0b1010"),
+            (NumericValue::Integer(1_000_000), NumericLiteralKind::Decimal, "1_000_000"),
+            (NumericValue::Integer(0xFF8000), NumericLiteralKind::Colour, "$FF8000"),
+            (NumericValue::Integer(9), NumericLiteralKind::Decimal, "9"),
+        ]
+    );
+}
+
+#[test]
+fn member_access_after_integer_is_not_a_real() {
+    let tokens = tokenize("arr[0].x").unwrap();
+
+    assert_eq!(tokens[4].data, TokenData::Dot);
+}
+
+#[test]
+fn numeric_literal_diagnostics() {
+    let err = tokenize("0xFG").unwrap_err();
+    assert!(matches!(err.data, TokenizeErrorData::InvalidNumericLiteral(ref txt) if txt == "0xFG"));
+
+    let err = tokenize("0b102").unwrap_err();
+    assert!(matches!(err.data, TokenizeErrorData::InvalidNumericLiteral(_)));
+
+    let err = tokenize("0x1_0000_0000_0000_0000").unwrap_err();
+    assert!(matches!(err.data, TokenizeErrorData::NumericLiteralOverflow(_)));
+
+    let err = tokenize("1e400").unwrap_err();
+    assert!(matches!(err.data, TokenizeErrorData::NumericLiteralOverflow(_)));
+
+    let err = tokenize("9007199254740993").unwrap_err();
+    assert!(matches!(err.data, TokenizeErrorData::NumericLiteralPrecisionLoss(_)));
+}
+
+#[test]
+fn comments_are_trivia() {
+    let tokens = tokenize("a // line\n/* outer /* inner */ still comment */ b //// not doc").unwrap();
+    let data: Vec<&TokenData> = tokens.iter().map(|tk| &tk.data).collect();
+
+    assert_eq!(
+        data,
+        vec![
+            &TokenData::Identifier("a".to_string()),
+            &TokenData::Identifier("b".to_string()),
+        ]
+    );
+    assert_eq!(tokens[1].text_range.start, TextPos { line: 2, pos: 39 });
+}
+
+#[test]
+fn doc_comments_are_tokens() {
+    let tokens = tokenize("/// Heals the player\nfn heal() {}").unwrap();
+
+    assert_eq!(tokens[0].data, TokenData::DocComment("Heals the player".to_string()));
+}
+
+#[test]
+fn doc_comments_inside_statements_are_trivia() {
+    for src in ["x = 1 + /// note\n 2;", "if a /// why\n{\n}", "f(a, /// first\nb);"] {
+        let tokens = tokenize(src).unwrap();
+        assert!(!tokens.iter().any(|tk| matches!(tk.data, TokenData::DocComment(_))), "{}", src);
+
+        let (_, errors) = parse_tks(&tokens);
+        assert!(errors.is_empty(), "{}: {:?}", src, errors);
+    }
+
+    let tokens = tokenize("x = 1;\n/// Health\n/// left\nvar hp = 3;").unwrap();
+    let docs = tokens.iter().filter(|tk| matches!(tk.data, TokenData::DocComment(_))).count();
+    assert_eq!(docs, 2);
+}
+
+#[test]
+fn unterminated_block_comment() {
+    let err = tokenize("a /* /* */").unwrap_err();
+
+    assert!(matches!(err.data, TokenizeErrorData::UnterminatedComment));
+}
+
+#[test]
+fn operator_tokens() {
+    let tokens = tokenize("<= >= << >> && || ^^ ! != % div & | ^ ~").unwrap();
+    let data: Vec<TokenData> = tokens.into_iter().map(|tk| tk.data).collect();
+
+    assert_eq!(
+        data,
+        vec![
+            TokenData::LessOrEquals,
+            TokenData::GreaterOrEquals,
+            TokenData::ShiftLeft,
+            TokenData::ShiftRight,
+            TokenData::And,
+            TokenData::Or,
+            TokenData::Xor,
+            TokenData::Not,
+            TokenData::NotEquals,
+            TokenData::Percent,
+            TokenData::IntDiv,
+            TokenData::Ampersand,
+            TokenData::Pipe,
+            TokenData::Caret,
+            TokenData::Tilde,
+        ]
+    );
+}
@@ -0,0 +1,162 @@
+use crate::{
+    checker::type_check::{check_types, InferredType, TypeError, TypeErrorData},
+    organizer::info::ProjectInfo,
+    parser::parse_tks,
+    tokenizer::tokenize,
+};
+
+fn type_errors(src: &str) -> Vec<TypeError> {
+    let tokens = tokenize(src).unwrap();
+    let (program, errors) = parse_tks(&tokens);
+    assert!(errors.is_empty(), "{:?}", errors);
+
+    check_types(&program, &ProjectInfo::default(), None)
+}
+
+#[test]
+fn well_typed_programs() {
+    let errors = type_errors(
+        "var name = \"a\" + \"b\";
+        var hp: Real = 10;
+        var alive = hp > 0 && name != \"\";
+        fn heal(amount: Real) -> Real {
+            return hp + amount;
+        }
+        if alive {
+            hp = heal(5) * 2;
+        }
+        for i in 0..hp {
+            instance_create(x, y, i);
+        }
+        var list: [Real] = (1, 2, 3);
+        for item in list {
+            hp -= item;
+        }",
+    );
+
+    assert!(errors.is_empty(), "{:?}", errors);
+}
+
+#[test]
+fn invalid_operands() {
+    let errors = type_errors("var name = \"a\";\nvar doubled = name * 2;");
+
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        &errors[0].data,
+        TypeErrorData::InvalidOperands(op, InferredType::String, InferredType::Real) if op == "*"
+    ));
+    assert_eq!(errors[0].text_range.start.line, 2);
+    assert_eq!(errors[0].text_range.start.pos, 15);
+}
+
+#[test]
+fn call_arity_and_arguments() {
+    let errors = type_errors(
+        "fn hurt(amount: Real, source: String) {}
+        hurt(1);
+        hurt(\"a\", \"b\");",
+    );
+
+    assert_eq!(errors.len(), 2);
+    assert!(matches!(
+        errors[0].data,
+        TypeErrorData::WrongArgumentCount(2, 1)
+    ));
+    assert!(matches!(
+        errors[1].data,
+        TypeErrorData::MismatchedTypes(InferredType::Real, InferredType::String)
+    ));
+    assert_eq!(errors[1].text_range.start.line, 3);
+}
+
+#[test]
+fn assignments_follow_declared_types() {
+    let errors = type_errors(
+        "var hp: Real = \"full\";
+        var name = \"a\";
+        name = 5;
+        name += 1;
+        fn reset() {}
+        var nothing = reset();",
+    );
+    let found: Vec<String> = errors.iter().map(|err| format!("{:?}", err.data)).collect();
+
+    assert_eq!(
+        found,
+        vec![
+            "MismatchedTypes(Real, String)",
+            "MismatchedTypes(String, Real)",
+            "InvalidOperands(\"+\", String, Real)",
+            "VoidValue",
+        ]
+    );
+}
+
+#[test]
+fn conditions_must_be_bool() {
+    let errors = type_errors(
+        "var hp = 10;
+        if hp {}
+        while hp > 0 {}
+        if place_meeting(x, y, obj_wall) {}",
+    );
+
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        errors[0].data,
+        TypeErrorData::MismatchedTypes(InferredType::Bool, InferredType::Real)
+    ));
+    assert_eq!(errors[0].text_range.start.line, 2);
+}
+
+#[test]
+fn returns_match_the_signature() {
+    let errors = type_errors(
+        "fn name() -> String {
+            return 1;
+        }",
+    );
+
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        errors[0].data,
+        TypeErrorData::MismatchedTypes(InferredType::String, InferredType::Real)
+    ));
+}
+
+#[test]
+fn untyped_functions_can_return_values() {
+    let errors = type_errors(
+        "fn add(a, b) {
+            return a + b;
+        }
+        fn pick(list) {
+            if list == 0 {
+                return;
+            }
+            fn inner() {
+                return 1;
+            }
+        }
+        let c = add(1, 2);
+        let d = pick(0);",
+    );
+    let found: Vec<String> = errors.iter().map(|err| format!("{:?}", err.data)).collect();
+
+    assert_eq!(found, vec!["VoidValue"]);
+    assert_eq!(errors[0].text_range.start.line, 13);
+}
+
+#[test]
+fn void_return_type() {
+    let errors = type_errors(
+        "fn reset() -> Void {
+            return;
+        }
+        let a = reset();",
+    );
+    let found: Vec<String> = errors.iter().map(|err| format!("{:?}", err.data)).collect();
+
+    assert_eq!(found, vec!["VoidValue"]);
+}
@@ -25,8 +25,8 @@ impl<'a> From<(&BorrowedTextRange<'a>, &BorrowedTextRange<'a>)> for BorrowedText
     fn from(value: (&BorrowedTextRange<'a>, &BorrowedTextRange<'a>)) -> BorrowedTextRange<'a> {
         let (start, end) = value;
         BorrowedTextRange {
-            start: &start.start,
-            end: &end.end,
+            start: start.start,
+            end: end.end,
         }
     }
 }
@@ -40,11 +40,20 @@ impl<'a> From<&'a TextRange> for BorrowedTextRange<'a> {
     }
 }
 
+/// Position of things with no source, like an empty program.
+static EMPTY_POS: TextPos = TextPos { line: 1, pos: 0 };
+
 impl<'a> From<&'a [Token]> for BorrowedTextRange<'a> {
     fn from(value: &'a [Token]) -> Self {
-        BorrowedTextRange {
-            start: &value[0].text_range.start,
-            end: &value.last().unwrap().text_range.end,
+        match (value.first(), value.last()) {
+            (Some(first), Some(last)) => BorrowedTextRange {
+                start: &first.text_range.start,
+                end: &last.text_range.end,
+            },
+            _ => BorrowedTextRange {
+                start: &EMPTY_POS,
+                end: &EMPTY_POS,
+            },
         }
     }
 }
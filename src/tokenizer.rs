@@ -1,6 +1,8 @@
+use std::fmt;
+
 use crate::ast::{PropertyAccessType, OperatorType, VariableModificationType};
 use crate::parser::utils::delimiter_checker::DelimiterChecker;
-use crate::text_data::{BorrowedTextRange, TextPos, TextRange};
+use crate::text_data::{TextPos, TextRange};
 use crate::Boxxable;
 
 #[derive(Debug, PartialEq, Clone)]
@@ -16,7 +18,7 @@ pub trait TokensUtils {
 }
 
 impl TokensUtils for [Token] {
-    fn split_tks<'a, T: AsRef<TokenData>>(&'a self, splitter: T) -> Vec<&'a [Token]> {
+    fn split_tks<T: AsRef<TokenData>>(&self, splitter: T) -> Vec<&[Token]> {
         let splitter = splitter.as_ref();
         let mut slices: Vec<&[Token]> = Vec::new();
         let mut last_idx: usize = 0;
@@ -61,7 +63,7 @@ impl TokensUtils for [Token] {
 
         let mut delimiter_checker = DelimiterChecker::new();
         for (i, tk) in self.iter().enumerate() {
-            delimiter_checker.check(&tk).ok()?;
+            delimiter_checker.check(tk).ok()?;
             if tk.data == *search_tk && delimiter_checker.is_free() {
                 return Some(i);
             }
@@ -128,9 +130,9 @@ impl TokensUtils for [Token] {
     }
 }
 
-impl ToString for Token {
-    fn to_string(&self) -> String {
-        match &self.data {
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let txt = match &self.data {
             TokenData::ModAccess => "::".to_string(),
             TokenData::NotEquals => "!=".to_string(),
             TokenData::IsEquals => "==".to_string(),
@@ -160,10 +162,74 @@ impl ToString for Token {
             TokenData::Fn => "fn".to_string(),
             TokenData::If => "if".to_string(),
             TokenData::Else => "else".to_string(),
+            TokenData::While => "while".to_string(),
+            TokenData::Loop => "loop".to_string(),
+            TokenData::Repeat => "repeat".to_string(),
+            TokenData::Do => "do".to_string(),
+            TokenData::Until => "until".to_string(),
+            TokenData::Break => "break".to_string(),
+            TokenData::Continue => "continue".to_string(),
+            TokenData::For => "for".to_string(),
+            TokenData::Return => "return".to_string(),
+            TokenData::Super => "super".to_string(),
+            TokenData::Arrow => "->".to_string(),
+            TokenData::Colon => ":".to_string(),
+            TokenData::In => "in".to_string(),
+            TokenData::DotDot => "..".to_string(),
+            TokenData::DotDotEquals => "..=".to_string(),
             TokenData::GreaterThan => ">".to_string(),
             TokenData::LessThan => "<".to_string(),
+            TokenData::GreaterOrEquals => ">=".to_string(),
+            TokenData::LessOrEquals => "<=".to_string(),
+            TokenData::ShiftLeft => "<<".to_string(),
+            TokenData::ShiftRight => ">>".to_string(),
+            TokenData::Percent => "%".to_string(),
+            TokenData::IntDiv => "div".to_string(),
+            TokenData::Ampersand => "&".to_string(),
+            TokenData::Caret => "^".to_string(),
+            TokenData::Tilde => "~".to_string(),
+            TokenData::Not => "!".to_string(),
+            TokenData::And => "&&".to_string(),
+            TokenData::Xor => "^^".to_string(),
             TokenData::Identifier(id) => id.clone(),
-            TokenData::NumericLiteral(num) => num.to_string(),
+            TokenData::NumericLiteral(num) => num.spelling.clone(),
+            TokenData::StringLiteral(txt) => format!("{:?}", txt),
+            TokenData::DocComment(txt) => format!("/// {}", txt),
+        };
+
+        f.write_str(&txt)
+    }
+}
+
+const MAX_EXACT_INTEGER: u64 = 1 << f64::MANTISSA_DIGITS;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NumericLiteral {
+    pub value: NumericValue,
+    pub kind: NumericLiteralKind,
+    pub spelling: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NumericValue {
+    Integer(u64),
+    Real(f64),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NumericLiteralKind {
+    Decimal,
+    Hex,
+    Binary,
+    Colour,
+}
+
+impl NumericLiteralKind {
+    pub fn radix(&self) -> u32 {
+        match self {
+            NumericLiteralKind::Decimal => 10,
+            NumericLiteralKind::Hex | NumericLiteralKind::Colour => 16,
+            NumericLiteralKind::Binary => 2,
         }
     }
 }
@@ -171,7 +237,9 @@ impl ToString for Token {
 #[derive(Debug, Clone, PartialEq)]
 pub enum TokenData {
     Identifier(String),
-    NumericLiteral(usize),
+    NumericLiteral(NumericLiteral),
+    StringLiteral(String),
+    DocComment(String),
     Equals,
     Add,
     Sub,
@@ -184,6 +252,19 @@ pub enum TokenData {
 
     GreaterThan,
     LessThan,
+    GreaterOrEquals,
+    LessOrEquals,
+    ShiftLeft,
+    ShiftRight,
+
+    Percent,
+    IntDiv,
+    Ampersand,
+    Caret,
+    Tilde,
+    Not,
+    And,
+    Xor,
 
     OpenParenthesis,
     CloseParenthesis,
@@ -197,6 +278,19 @@ pub enum TokenData {
     Fn,
     If,
     Else,
+    While,
+    Loop,
+    Repeat,
+    Do,
+    Until,
+    Break,
+    Continue,
+    For,
+    In,
+    Return,
+    Super,
+    Arrow,
+    Colon,
     ModAccess,
     NotEquals,
     IsEquals,
@@ -204,6 +298,8 @@ pub enum TokenData {
     Semilicon,
     Pipe,
     Dot,
+    DotDot,
+    DotDotEquals,
     Or,
 }
 
@@ -217,10 +313,44 @@ impl TokenData {
             TokenData::NotEquals => Some(OperatorType::NotEquals),
             TokenData::IsEquals => Some(OperatorType::IsEquals),
             TokenData::Or => Some(OperatorType::Or),
+            TokenData::Percent => Some(OperatorType::Mod),
+            TokenData::IntDiv => Some(OperatorType::IntDiv),
+            TokenData::GreaterThan => Some(OperatorType::GreaterThan),
+            TokenData::LessThan => Some(OperatorType::LessThan),
+            TokenData::GreaterOrEquals => Some(OperatorType::GreaterOrEquals),
+            TokenData::LessOrEquals => Some(OperatorType::LessOrEquals),
+            TokenData::And => Some(OperatorType::And),
+            TokenData::Xor => Some(OperatorType::Xor),
+            TokenData::Ampersand => Some(OperatorType::BitAnd),
+            TokenData::Pipe => Some(OperatorType::BitOr),
+            TokenData::Caret => Some(OperatorType::BitXor),
+            TokenData::ShiftLeft => Some(OperatorType::ShiftLeft),
+            TokenData::ShiftRight => Some(OperatorType::ShiftRight),
             _ => None,
         }
     }
 
+    /// Tokens that can only start a statement, used to resynchronize after errors.
+    pub fn is_statement_keyword(&self) -> bool {
+        matches!(
+            self,
+            TokenData::Fn
+                | TokenData::If
+                | TokenData::Const
+                | TokenData::Var
+                | TokenData::Let
+                | TokenData::While
+                | TokenData::Loop
+                | TokenData::Repeat
+                | TokenData::Do
+                | TokenData::Break
+                | TokenData::Continue
+                | TokenData::For
+                | TokenData::Return
+                | TokenData::Super
+        )
+    }
+
     pub fn variable_modification_type(&self) -> Option<VariableModificationType> {
         match self {
             TokenData::Equals => Some(VariableModificationType::Set),
@@ -247,12 +377,19 @@ impl AsRef<TokenData> for TokenData {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum TokenizeErrorData {
     UnknownCharacter(char),
+    UnterminatedString,
+    InvalidEscapeSequence(char),
+    InvalidUnicodeEscape(String),
+    UnterminatedComment,
+    InvalidNumericLiteral(String),
+    NumericLiteralOverflow(String),
+    NumericLiteralPrecisionLoss(String),
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct TokenizeError {
     pub data: TokenizeErrorData,
     pub text_range: TextRange,
@@ -267,60 +404,65 @@ pub fn tokenize(src: &str) -> Result<Vec<Token>, TokenizeError> {
     let recognizers: &[Box<dyn TokenRecognizer>] = &[
         IdetifierRecognizer {}.to_box(),
         NumericLiteralRecognizer {}.to_box(),
+        StringLiteralRecognizer {}.to_box(),
+        DocCommentRecognizer {}.to_box(),
         SymbolRecognizer::new().to_box(),
     ];
 
     while ptr < src.len() {
-        let chr = &src.chars().nth(ptr).expect("Ptr was outside of src");
+        let chr = src[ptr..].chars().next().expect("Ptr was outside of src");
 
-        if *chr == '\n' {
-            line += 1;
-            pos = 0;
-        } else {
-            pos += 1;
+        if [' ', '\t', '\r', '\n'].contains(&chr) {
+            if chr == '\n' {
+                line += 1;
+                pos = 0;
+            } else {
+                pos += 1;
+            }
+
+            ptr += chr.len_utf8();
+            continue;
         }
 
-        if [' ', '\t', '\n'].contains(&chr) {
-            ptr += 1;
+        let comment_len = comment_length(&src[ptr..]).map_err(|data| TokenizeError {
+            data,
+            text_range: TextRange::from((line, pos + 1)),
+        })?;
+        if let Some(len) = comment_len {
+            advance_text_pos(&src[ptr..ptr + len], &mut line, &mut pos);
+            ptr += len;
             continue;
         }
 
+        let start = TextPos { line, pos: pos + 1 };
+
         let recognizer = recognizers
             .iter()
             .find(|rec| rec.recognize(&src[ptr..]))
             .ok_or_else(|| {
                 TokenizeError {
-                    data: TokenizeErrorData::UnknownCharacter(chr.clone()),
-                    text_range: TextRange::from((line.clone(), pos.clone())),
+                    data: TokenizeErrorData::UnknownCharacter(chr),
+                    text_range: TextRange::from((line, pos + 1)),
                 }
             })?;
-        let (token_data, numb) = recognizer.get_token(&src[ptr..]);
+        let (token_data, numb) = recognizer.get_token(&src[ptr..]).map_err(|data| {
+            TokenizeError {
+                data,
+                text_range: TextRange::from((line, pos + 1)),
+            }
+        })?;
         assert_ne!(numb, 0);
 
-        let start_line = line.clone();
-        let start_pos = pos.clone();
-        let mut end_line = line.clone();
-        let mut end_pos = pos.clone();
+        advance_text_pos(&src[ptr..ptr + numb], &mut line, &mut pos);
+        ptr += numb;
 
-        for _ in 0..numb - 1 {
-            if let Some('\n') = src.chars().nth(numb) {
-                end_line += 1;
-                end_pos = 0;
-            } else {
-                end_pos += 1;
-            }
+        // Only doc comments before a statement document something, the rest
+        // are plain comments.
+        if matches!(token_data, TokenData::DocComment(_)) && !at_statement_start(&tokens) {
+            continue;
         }
 
-        ptr += numb;
-
-        let start = TextPos {
-            line: start_line,
-            pos: start_pos,
-        };
-        let end = TextPos {
-            line: end_line,
-            pos: end_pos,
-        };
+        let end = TextPos { line, pos };
 
         let token = Token {
             data: token_data,
@@ -332,21 +474,79 @@ pub fn tokenize(src: &str) -> Result<Vec<Token>, TokenizeError> {
     Ok(tokens)
 }
 
+/// Whether a statement can start after `tokens`, judging by the last one.
+fn at_statement_start(tokens: &[Token]) -> bool {
+    matches!(
+        tokens.last().map(|tk| &tk.data),
+        None | Some(
+            TokenData::Semilicon | TokenData::OpenCurly | TokenData::CloseCurly | TokenData::DocComment(_)
+        )
+    )
+}
+
+fn advance_text_pos(text: &str, line: &mut usize, pos: &mut usize) {
+    for chr in text.chars() {
+        if chr == '\n' {
+            *line += 1;
+            *pos = 0;
+        } else {
+            *pos += 1;
+        }
+    }
+}
+
+fn is_doc_comment(code_left: &str) -> bool {
+    code_left.starts_with("///") && !code_left.starts_with("////")
+}
+
+/// Byte length of the comment at the start of `code_left`, if there is one.
+/// Doc comments aren't trivia, they are turned into tokens by `DocCommentRecognizer`
+/// when they come before a statement.
+fn comment_length(code_left: &str) -> Result<Option<usize>, TokenizeErrorData> {
+    if code_left.starts_with("//") && !is_doc_comment(code_left) {
+        return Ok(Some(code_left.find('\n').unwrap_or(code_left.len())));
+    }
+
+    if !code_left.starts_with("/*") {
+        return Ok(None);
+    }
+
+    let mut depth: usize = 0;
+    let mut ptr: usize = 0;
+    while ptr < code_left.len() {
+        if code_left[ptr..].starts_with("/*") {
+            depth += 1;
+            ptr += 2;
+        } else if code_left[ptr..].starts_with("*/") {
+            depth -= 1;
+            ptr += 2;
+
+            if depth == 0 {
+                return Ok(Some(ptr));
+            }
+        } else {
+            ptr += code_left[ptr..].chars().next().map_or(1, char::len_utf8);
+        }
+    }
+
+    Err(TokenizeErrorData::UnterminatedComment)
+}
+
 trait TokenRecognizer {
     fn recognize(&self, _: &str) -> bool;
-    fn get_token(&self, _: &str) -> (TokenData, usize);
+    fn get_token(&self, _: &str) -> Result<(TokenData, usize), TokenizeErrorData>;
 }
 
 struct IdetifierRecognizer;
 impl TokenRecognizer for IdetifierRecognizer {
     fn recognize(&self, code_left: &str) -> bool {
-        matches!(code_left.chars().nth(0).expect("Code is empty"), 'a'..'z' | 'A'..'Z' | '_')
+        matches!(code_left.chars().nth(0).expect("Code is empty"), 'a'..='z' | 'A'..='Z' | '_')
     }
 
-    fn get_token(&self, code_left: &str) -> (TokenData, usize) {
+    fn get_token(&self, code_left: &str) -> Result<(TokenData, usize), TokenizeErrorData> {
         let id: String = code_left
             .chars()
-            .take_while(|c| matches!(c, 'a'..'z' | 'A'..'Z' | '_' | '0'..'9'))
+            .take_while(|c| matches!(c, 'a'..='z' | 'A'..='Z' | '_' | '0'..='9'))
             .collect();
         let len = id.len();
 
@@ -357,33 +557,202 @@ impl TokenRecognizer for IdetifierRecognizer {
             "fn" => TokenData::Fn,
             "if" => TokenData::If,
             "else" => TokenData::Else,
+            "while" => TokenData::While,
+            "loop" => TokenData::Loop,
+            "repeat" => TokenData::Repeat,
+            "do" => TokenData::Do,
+            "until" => TokenData::Until,
+            "break" => TokenData::Break,
+            "continue" => TokenData::Continue,
+            "for" => TokenData::For,
+            "return" => TokenData::Return,
+            "super" => TokenData::Super,
+            "in" => TokenData::In,
+            "div" => TokenData::IntDiv,
             _ => TokenData::Identifier(id),
         };
 
-        (token_data, len)
+        Ok((token_data, len))
     }
 }
 
 struct NumericLiteralRecognizer;
 impl TokenRecognizer for NumericLiteralRecognizer {
     fn recognize(&self, code_left: &str) -> bool {
-        matches!(code_left.chars().nth(0).expect("Empty code"), '0'..'9')
+        let mut chars = code_left.chars();
+        match chars.next().expect("Empty code") {
+            '$' => chars.next().is_some_and(|c| c.is_ascii_hexdigit()),
+            chr => chr.is_ascii_digit(),
+        }
     }
 
-    fn get_token(&self, code_left: &str) -> (TokenData, usize) {
-        let id: String = code_left
+    fn get_token(&self, code_left: &str) -> Result<(TokenData, usize), TokenizeErrorData> {
+        let bytes = code_left.as_bytes();
+        let take_digits = |from: usize, is_digit: fn(&u8) -> bool| {
+            from + bytes[from..]
+                .iter()
+                .take_while(|b| is_digit(b) || **b == b'_')
+                .count()
+        };
+
+        let (kind, digits_start) = match (bytes[0], bytes.get(1)) {
+            (b'$', _) => (NumericLiteralKind::Colour, 1),
+            (b'0', Some(b'x' | b'X')) => (NumericLiteralKind::Hex, 2),
+            (b'0', Some(b'b' | b'B')) => (NumericLiteralKind::Binary, 2),
+            _ => (NumericLiteralKind::Decimal, 0),
+        };
+        let mut len = match kind {
+            NumericLiteralKind::Decimal => take_digits(0, u8::is_ascii_digit),
+            NumericLiteralKind::Binary => take_digits(2, |b| matches!(b, b'0' | b'1')),
+            NumericLiteralKind::Hex | NumericLiteralKind::Colour => {
+                take_digits(digits_start, u8::is_ascii_hexdigit)
+            }
+        };
+
+        let mut is_real = false;
+        if let NumericLiteralKind::Decimal = kind {
+            let has_fraction = bytes.get(len + 1).is_some_and(u8::is_ascii_digit);
+            if bytes.get(len) == Some(&b'.') && has_fraction {
+                is_real = true;
+                len = take_digits(len + 1, u8::is_ascii_digit);
+            }
+
+            if matches!(bytes.get(len), Some(b'e' | b'E')) {
+                let exponent_start = match bytes.get(len + 1) {
+                    Some(b'+' | b'-') => len + 2,
+                    _ => len + 1,
+                };
+
+                if bytes.get(exponent_start).is_some_and(u8::is_ascii_digit) {
+                    is_real = true;
+                    len = take_digits(exponent_start, u8::is_ascii_digit);
+                }
+            }
+        }
+
+        let spelling = code_left[..len].to_string();
+        let digits: String = code_left[digits_start..len]
             .chars()
-            .take_while(|c| matches!(c, '0'..'9'))
+            .filter(|c| *c != '_')
             .collect();
-        let len = id.len();
 
-        (
-            TokenData::NumericLiteral(id.parse().expect("Numeric Literal error")),
+        let is_word_char = |b: &u8| b.is_ascii_alphanumeric() || *b == b'_';
+        if digits.is_empty() || bytes.get(len).is_some_and(is_word_char) {
+            let end = len + bytes[len..].iter().take_while(|b| is_word_char(b)).count();
+            return Err(TokenizeErrorData::InvalidNumericLiteral(
+                code_left[..end].to_string(),
+            ));
+        }
+
+        let value = match kind {
+            NumericLiteralKind::Decimal if is_real => {
+                let value: f64 = digits.parse().expect("Real literal was validated above");
+                if value.is_infinite() {
+                    return Err(TokenizeErrorData::NumericLiteralOverflow(spelling));
+                }
+
+                NumericValue::Real(value)
+            }
+            _ => {
+                let value = u64::from_str_radix(&digits, kind.radix())
+                    .map_err(|_| TokenizeErrorData::NumericLiteralOverflow(spelling.clone()))?;
+
+                // GameMaker stores plain decimal numbers as doubles, so anything past 2^53
+                // would silently round in the generated code.
+                if let NumericLiteralKind::Decimal = kind {
+                    if value > MAX_EXACT_INTEGER {
+                        return Err(TokenizeErrorData::NumericLiteralPrecisionLoss(spelling));
+                    }
+                }
+
+                NumericValue::Integer(value)
+            }
+        };
+
+        Ok((
+            TokenData::NumericLiteral(NumericLiteral { value, kind, spelling }),
             len,
-        )
+        ))
     }
 }
 
+struct DocCommentRecognizer;
+impl TokenRecognizer for DocCommentRecognizer {
+    fn recognize(&self, code_left: &str) -> bool {
+        is_doc_comment(code_left)
+    }
+
+    fn get_token(&self, code_left: &str) -> Result<(TokenData, usize), TokenizeErrorData> {
+        let len = code_left.find('\n').unwrap_or(code_left.len());
+        let txt = code_left[3..len].trim_end_matches('\r');
+        let txt = txt.strip_prefix(' ').unwrap_or(txt);
+
+        Ok((TokenData::DocComment(txt.to_string()), len))
+    }
+}
+
+struct StringLiteralRecognizer;
+impl TokenRecognizer for StringLiteralRecognizer {
+    fn recognize(&self, code_left: &str) -> bool {
+        code_left.starts_with('"')
+    }
+
+    fn get_token(&self, code_left: &str) -> Result<(TokenData, usize), TokenizeErrorData> {
+        let mut value = String::new();
+        let mut chars = code_left.char_indices().skip(1);
+
+        while let Some((i, chr)) = chars.next() {
+            match chr {
+                '"' => return Ok((TokenData::StringLiteral(value), i + 1)),
+                '\\' => {
+                    let (_, escaped) = chars.next().ok_or(TokenizeErrorData::UnterminatedString)?;
+                    let escaped = match escaped {
+                        'n' => '\n',
+                        't' => '\t',
+                        'r' => '\r',
+                        '0' => '\0',
+                        '"' => '"',
+                        '\\' => '\\',
+                        'u' => parse_unicode_escape(&mut chars)?,
+                        _ => return Err(TokenizeErrorData::InvalidEscapeSequence(escaped)),
+                    };
+                    value.push(escaped);
+                }
+                _ => value.push(chr),
+            }
+        }
+
+        Err(TokenizeErrorData::UnterminatedString)
+    }
+}
+
+fn parse_unicode_escape(
+    chars: &mut impl Iterator<Item = (usize, char)>,
+) -> Result<char, TokenizeErrorData> {
+    let mut digits = String::new();
+
+    if !matches!(chars.next(), Some((_, '{'))) {
+        return Err(TokenizeErrorData::InvalidUnicodeEscape(digits));
+    }
+
+    loop {
+        match chars.next() {
+            Some((_, '}')) => break,
+            Some((_, '"')) | None => return Err(TokenizeErrorData::InvalidUnicodeEscape(digits)),
+            Some((_, chr)) => digits.push(chr),
+        }
+    }
+
+    if digits.is_empty() || digits.len() > 6 {
+        return Err(TokenizeErrorData::InvalidUnicodeEscape(digits));
+    }
+
+    u32::from_str_radix(&digits, 16)
+        .ok()
+        .and_then(char::from_u32)
+        .ok_or(TokenizeErrorData::InvalidUnicodeEscape(digits))
+}
+
 struct SymbolRecognizer {
     vec: Vec<(String, TokenData)>,
 }
@@ -392,19 +761,34 @@ impl SymbolRecognizer {
     pub fn new() -> Self {
         SymbolRecognizer {
             vec: Vec::from([
+                ("..=".to_string(), TokenData::DotDotEquals),
                 ("::".to_string(), TokenData::ModAccess),
+                (":".to_string(), TokenData::Colon),
+                ("..".to_string(), TokenData::DotDot),
                 ("!=".to_string(), TokenData::NotEquals),
                 ("==".to_string(), TokenData::IsEquals),
                 ("+=".to_string(), TokenData::IncreaseBy),
                 ("-=".to_string(), TokenData::DecreaseBy),
+                ("->".to_string(), TokenData::Arrow),
                 ("*=".to_string(), TokenData::MultiplyBy),
                 ("/=".to_string(), TokenData::DivideBy),
                 ("||".to_string(), TokenData::Or),
+                ("&&".to_string(), TokenData::And),
+                ("^^".to_string(), TokenData::Xor),
+                ("<<".to_string(), TokenData::ShiftLeft),
+                (">>".to_string(), TokenData::ShiftRight),
+                ("<=".to_string(), TokenData::LessOrEquals),
+                (">=".to_string(), TokenData::GreaterOrEquals),
                 ("=".to_string(), TokenData::Equals),
                 ("+".to_string(), TokenData::Add),
                 ("-".to_string(), TokenData::Sub),
                 ("*".to_string(), TokenData::Mul),
                 ("/".to_string(), TokenData::Div),
+                ("%".to_string(), TokenData::Percent),
+                ("&".to_string(), TokenData::Ampersand),
+                ("^".to_string(), TokenData::Caret),
+                ("~".to_string(), TokenData::Tilde),
+                ("!".to_string(), TokenData::Not),
                 ("(".to_string(), TokenData::OpenParenthesis),
                 (")".to_string(), TokenData::CloseParenthesis),
                 ("[".to_string(), TokenData::OpenBracket),
@@ -424,17 +808,17 @@ impl SymbolRecognizer {
 
 impl TokenRecognizer for SymbolRecognizer {
     fn recognize(&self, code_left: &str) -> bool {
-        self.vec.iter().any(|(k, _)| code_left[0..k.len()] == *k)
+        self.vec.iter().any(|(k, _)| code_left.starts_with(k.as_str()))
     }
 
-    fn get_token(&self, code_left: &str) -> (TokenData, usize) {
+    fn get_token(&self, code_left: &str) -> Result<(TokenData, usize), TokenizeErrorData> {
         let idx = self
             .vec
             .iter()
-            .position(|(k, _)| code_left[0..k.len()] == **k)
+            .position(|(k, _)| code_left.starts_with(k.as_str()))
             .expect("Symbol not found");
         let (key, token_data) = self.vec.get(idx).expect("Token Data not found");
 
-        (token_data.clone(), key.len())
+        Ok((token_data.clone(), key.len()))
     }
 }